@@ -6,25 +6,36 @@
 
 use std::{
     env,
-    fs::File,
     io::{self, Write},
 };
 
 /// Runs the Bayesian plug-in rule and outputs the results.
 ///
 /// Program input is the filename of the training data, and the
-/// filename of the test data.
+/// filename of the test data. With `--cv k`, only the training
+/// data is given, and k-fold cross-validation is run on it instead.
 fn main() {
     // get program arguments
-    let args: Vec<_> = env::args().collect();
-    if args.len() != 3 {
+    let mut args: Vec<_> = env::args().collect();
+    // pull out output data file name, if given
+    let output_data_file_name = data_utils::args::take_option(&mut args, "--output");
+    // pull out output format, if given
+    let format = data_utils::args::take_option(&mut args, "--format");
+    // pull out number of cross-validation folds, if given
+    let folds = data_utils::args::take_option(&mut args, "--cv");
+    if args.len() != if folds.is_some() { 2 } else { 3 } {
         /* invalid number of arguments, print a help message */
         let mut lock = io::stdout().lock();
         writeln!(lock, "Bayesian Plug-In Rule").unwrap();
         writeln!(lock, "Author: Benjamin Hall").unwrap();
         writeln!(
             lock,
-            "Usage: ./color_class_bayes [train data filename] [test data filename]"
+            "Usage: ./color_class_bayes [train data filename] [test data filename] [--output output filename] [--format json]"
+        )
+        .unwrap();
+        writeln!(
+            lock,
+            "       ./color_class_bayes [data filename] --cv [num folds]"
         )
         .unwrap();
         writeln!(lock).unwrap();
@@ -39,6 +50,31 @@ fn main() {
             "The data can be n-dimensional, but the dimensions of the training data and of the test data should match."
         )
         .unwrap();
+        writeln!(
+            lock,
+            "A data filename of - reads that data from stdin, so only one of the data files can be -."
+        )
+        .unwrap();
+        writeln!(
+            lock,
+            "With the serde feature, the results can be printed as JSON rather than the default debug format."
+        )
+        .unwrap();
+        writeln!(
+            lock,
+            "If every test data point is labeled with a classification from the training data, the accuracy of each classification is also printed."
+        )
+        .unwrap();
+        writeln!(
+            lock,
+            "If an output file is given, the test data is written to it with the guessed classifications, one data point per line."
+        )
+        .unwrap();
+        writeln!(
+            lock,
+            "With cv, the labeled data is split into the given number of folds, and the mean accuracy of classifying each fold using the rest of the data is printed."
+        )
+        .unwrap();
 
         return;
     }
@@ -50,57 +86,150 @@ fn main() {
         return;
     }
 
-    // pull out test data file name
-    let test_data_file_name = args[2].as_str();
-    if test_data_file_name.is_empty() {
+    // pull out test data file name, unless cross-validating
+    let test_data_file_name = args.get(2).map_or("", String::as_str);
+    if folds.is_none() && test_data_file_name.is_empty() {
         println!("Error: no test data specified");
         return;
     }
 
-    // parse training data
-    let train_data = {
-        // open and read file
-        let train_data_file = File::open(train_data_file_name);
-        let Ok(train_data_file_contents) = data_utils::io::read_file(train_data_file) else {
-            println!("Error: could not read training data");
+    // stdin can only be read once
+    if train_data_file_name == "-" && test_data_file_name == "-" {
+        println!("Error: only one data file can be read from stdin");
+        return;
+    }
+
+    // check output format
+    let json = match format.as_deref() {
+        None => false,
+        Some("json") if cfg!(feature = "serde") => true,
+        Some("json") => {
+            println!("Error: JSON output requires the serde feature");
             return;
-        };
+        }
+        Some(_) => {
+            println!("Error: unknown output format");
+            return;
+        }
+    };
 
-        // map lines to DataPoints
-        let train_data: Result<Vec<_>, _> = train_data_file_contents
-            .lines()
-            .map(data_utils::DataPoint::try_from)
-            .collect();
-        let Ok(train_data) = train_data else {
-            println!("Error: could not parse training data");
+    // check output data file name
+    if output_data_file_name.as_deref() == Some("") {
+        println!("Error: no output file specified");
+        return;
+    }
+
+    // the output file is data, so it cannot also be JSON
+    if json && output_data_file_name.is_some() {
+        println!("Error: JSON output cannot be combined with an output file");
+        return;
+    }
+
+    // cross-validation only prints the mean accuracy
+    if folds.is_some() && (output_data_file_name.is_some() || format.is_some()) {
+        println!("Error: cross-validation does not support output options");
+        return;
+    }
+
+    // pull out number of cross-validation folds
+    let folds = match folds.map(|folds| folds.parse::<usize>()) {
+        None => None,
+        Some(Ok(folds)) if folds >= 2 => Some(folds),
+        Some(_) => {
+            println!("Error: invalid number of folds");
             return;
-        };
-        train_data
+        }
+    };
+
+    // parse training data
+    let train_data = {
+        // open and read file (or stdin)
+        let train_data_file_contents =
+            match data_utils::io::read_file_or_stdin(train_data_file_name) {
+                Ok(contents) => contents,
+                Err(e) => {
+                    println!("Error: could not read training data: {e}");
+                    return;
+                }
+            };
+
+        // map lines to DataPoints, reporting the line that could not be parsed
+        match data_utils::io::parse_data_points::<String>(&train_data_file_contents) {
+            Ok(train_data) => train_data,
+            Err(e) => {
+                println!("Error: could not parse training data: {e}");
+                return;
+            }
+        }
     };
 
+    if let Some(folds) = folds {
+        if folds > train_data.len() {
+            println!("Error: more folds than data points");
+            return;
+        }
+
+        // run Bayesian plug-in rule on each fold
+        let accuracy =
+            data_utils::classify::cross_validate(&train_data, folds, |train_data, test_data| {
+                data_utils::classify::bayes_plug_in(train_data, test_data)
+            });
+        println!("Mean accuracy over {folds} folds: {accuracy:.4}");
+        return;
+    }
+
     // parse test data
     let test_data = {
-        // open and read file
-        let test_data_file = File::open(test_data_file_name);
-        let Ok(test_data_file_contents) = data_utils::io::read_file(test_data_file) else {
-            println!("Error: could not read test data");
-            return;
+        // open and read file (or stdin)
+        let test_data_file_contents = match data_utils::io::read_file_or_stdin(test_data_file_name)
+        {
+            Ok(contents) => contents,
+            Err(e) => {
+                println!("Error: could not read test data: {e}");
+                return;
+            }
         };
 
-        // map lines to DataPoints
-        let test_data: Result<Vec<_>, _> = test_data_file_contents
-            .lines()
-            .map(data_utils::DataPoint::<String>::try_from)
-            .collect();
-        let Ok(test_data) = test_data else {
-            println!("Error: could not parse test data");
-            return;
-        };
-        test_data
+        // map lines to DataPoints, reporting the line that could not be parsed
+        match data_utils::io::parse_data_points::<String>(&test_data_file_contents) {
+            Ok(test_data) => test_data,
+            Err(e) => {
+                println!("Error: could not parse test data: {e}");
+                return;
+            }
+        }
     };
 
     // run Bayesian plug-in rule
     let test_res = data_utils::classify::bayes_plug_in(&train_data, &test_data);
-    // print the results (formatted)
-    println!("{test_res:#?}");
+
+    if let Some(output_data_file_name) = output_data_file_name {
+        // write the test data with the guessed classifications
+        let output_data: Vec<_> = test_res
+            .iter()
+            .map(|c| data_utils::DataPoint {
+                class: c.class_guess.clone(),
+                ..c.data.clone()
+            })
+            .collect();
+        if data_utils::io::write_data_points(output_data_file_name, &output_data).is_err() {
+            println!("Error: could not write output file");
+        }
+    } else if json {
+        // print the results as JSON
+        #[cfg(feature = "serde")]
+        println!("{}", serde_json::to_string_pretty(&test_res).unwrap());
+        return;
+    } else {
+        // print the results (formatted)
+        println!("{test_res:#?}");
+    }
+
+    // report the accuracy if the test data is labeled with the training classifications
+    if let Some(report) =
+        data_utils::classify::labeled_classification_report(&train_data, &test_res)
+    {
+        println!();
+        println!("{report}");
+    }
 }
@@ -0,0 +1,206 @@
+//! Tests running the Bayesian plug-in rule program.
+//!
+//! Author: Benjamin Hall
+
+use std::{
+    env, fs,
+    io::Write,
+    ops::Deref,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+};
+
+use data_utils::DataPoint;
+
+/// Gets the path of a data file in the root of the repository.
+fn data_path(name: &str) -> PathBuf {
+    [env!("CARGO_MANIFEST_DIR"), "..", "..", name]
+        .iter()
+        .collect()
+}
+
+/// Reads and parses a data file.
+fn read_data(path: &Path) -> Vec<DataPoint<String>> {
+    let contents = data_utils::io::read_file_from_path(path).unwrap();
+    data_utils::io::parse_data_points(&contents).unwrap()
+}
+
+/// A temporary file unique to this process, which is removed
+/// when dropped so it is cleaned up even if a test fails.
+struct TempFile(PathBuf);
+
+impl TempFile {
+    /// Gets a temporary file with the given name, without creating it.
+    fn new(name: &str) -> Self {
+        Self(env::temp_dir().join(format!("color_class_bayes_{}_{name}", std::process::id())))
+    }
+}
+
+impl Deref for TempFile {
+    type Target = Path;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        // the file may not have been created
+        let _ = fs::remove_file(&self.0);
+    }
+}
+
+/// Writes a temporary data file unique to this process.
+fn write_temp(name: &str, contents: &str) -> TempFile {
+    let file = TempFile::new(name);
+    fs::write(&*file, contents).unwrap();
+    file
+}
+
+/// Runs the program on the given data files with
+/// the given options, returning its output.
+fn run(train_path: &Path, test_path: &Path, options: &[&str]) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_color_class_bayes"))
+        .args([train_path, test_path])
+        .args(options)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn output_file() {
+    let train_path = data_path("color_training_full.dat");
+    let test_path = data_path("color_test_full.dat");
+    let output_path = TempFile::new("output.dat");
+
+    let status = Command::new(env!("CARGO_BIN_EXE_color_class_bayes"))
+        .args([&train_path, &test_path])
+        .arg("--output")
+        .arg(&*output_path)
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let output_data = read_data(&output_path);
+
+    // the output is the test data with the guessed classifications
+    let train_data = read_data(&train_path);
+    let test_data = read_data(&test_path);
+    let expected: Vec<_> = data_utils::classify::bayes_plug_in(&train_data, &test_data)
+        .iter()
+        .map(|c| (c.data.point.clone(), c.class_guess.clone()))
+        .collect();
+    let output: Vec<_> = output_data
+        .into_iter()
+        .map(|d| (d.point, d.class))
+        .collect();
+    assert_eq!(output, expected);
+}
+
+#[test]
+fn accuracy_report() {
+    let train_path = write_temp("train.dat", "0 0 red\n1 1 red\n10 10 blue\n11 11 blue\n");
+    // three of the four test data points are classified correctly
+    let test_path = write_temp(
+        "test.dat",
+        "0.5 0.5 red\n10.5 10.5 blue\n9 9 blue\n2 2 blue\n",
+    );
+    let unlabeled_path = write_temp("unlabeled.dat", "0.5 0.5 ?\n10.5 10.5 ?\n");
+
+    let output = run(&train_path, &test_path, &[]);
+    let unlabeled_output = run(&train_path, &unlabeled_path, &[]);
+
+    let accuracy_line = output.lines().last().unwrap();
+    assert!(accuracy_line.trim_start().starts_with("accuracy"));
+    assert!(accuracy_line.contains("0.75"));
+    assert!(output.contains("precision"));
+
+    // unlabeled test data has no accuracy to report
+    assert!(!unlabeled_output.contains("accuracy"));
+}
+
+#[test]
+fn cross_validation() {
+    let data_path = data_path("color_training_full.dat");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_color_class_bayes"))
+        .arg(&data_path)
+        .args(["--cv", "5"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let output = String::from_utf8(output.stdout).unwrap();
+
+    // the printed accuracy matches running cross-validation directly
+    let data = read_data(&data_path);
+    let accuracy = data_utils::classify::cross_validate(&data, 5, |train_data, test_data| {
+        data_utils::classify::bayes_plug_in(train_data, test_data)
+    });
+    assert_eq!(
+        output.trim(),
+        format!("Mean accuracy over 5 folds: {accuracy:.4}")
+    );
+    assert!(accuracy > 0.5);
+}
+
+#[test]
+fn stdin_data() {
+    let train_path = data_path("color_training_full.dat");
+    let test_path = data_path("color_test_full.dat");
+    let test_contents = fs::read_to_string(&test_path).unwrap();
+
+    // pipe the test data into the program
+    let mut child = Command::new(env!("CARGO_BIN_EXE_color_class_bayes"))
+        .arg(&train_path)
+        .arg("-")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(test_contents.as_bytes())
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success());
+
+    // the results match reading the test data from its file
+    let output = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(output, run(&train_path, &test_path, &[]));
+    assert!(output.contains("accuracy"));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn json_format() {
+    let train_path = data_path("color_training_full.dat");
+    let test_path = data_path("color_test_full.dat");
+
+    let output = run(&train_path, &test_path, &["--format", "json"]);
+    let results: serde_json::Value = serde_json::from_str(&output).unwrap();
+
+    let results = results.as_array().unwrap();
+    assert_eq!(results.len(), read_data(&test_path).len());
+    for result in results {
+        for field in ["point", "class", "class_guess", "confidence"] {
+            assert!(result.get(field).is_some());
+        }
+    }
+    assert_eq!(results[0]["class"], "red");
+
+    // JSON is not written to an output file
+    let output_path = TempFile::new("json_output.dat");
+    let output_arg = output_path.to_str().unwrap();
+    let output = run(
+        &train_path,
+        &test_path,
+        &["--format", "json", "--output", output_arg],
+    );
+    assert!(output.starts_with("Error: JSON output cannot be combined with an output file"));
+    assert!(!output_path.exists());
+}
@@ -7,10 +7,11 @@
 
 use std::{
     env,
-    fs::File,
     io::{self, Write},
 };
 
+use data_utils::classify::PerceptronConfig;
+
 /// Runs the Single-Layer Perceptron algorithm and outputs the results.
 /// Data is only classified into two groups.
 ///
@@ -18,15 +19,19 @@ use std::{
 /// of the test data, and the number of neighbors used in the algorithm.
 fn main() {
     // get program arguments
-    let args: Vec<_> = env::args().collect();
-    if args.len() < 3 || args.len() > 5 {
+    let mut args: Vec<_> = env::args().collect();
+    // pull out output data file name, if given
+    let output_data_file_name = data_utils::args::take_option(&mut args, "--output");
+    // pull out output format, if given
+    let format = data_utils::args::take_option(&mut args, "--format");
+    if args.len() < 3 || args.len() > 6 {
         /* invalid number of arguments, print a help message */
         let mut lock = io::stdout().lock();
         writeln!(lock, "Binary Single-Layer Perceptron").unwrap();
         writeln!(lock, "Author: Benjamin Hall").unwrap();
         writeln!(
             lock,
-            "Usage: ./color_class_binary_slp [train data filename] [test data filename] [learning rate = 1.0] [threshold = 0.0]"
+            "Usage: ./color_class_binary_slp [train data filename] [test data filename] [learning rate = 1.0] [threshold = 0.0] [seed = random] [--output output filename] [--format json]"
         )
         .unwrap();
         writeln!(lock).unwrap();
@@ -40,11 +45,36 @@ fn main() {
             lock,
             "The training threshold specifies the percentage of the training data that can be misclassified before stopping training."
         ).unwrap();
+        writeln!(
+            lock,
+            "The seed makes the random weights and shuffling of the training data reproducible across runs."
+        )
+        .unwrap();
         writeln!(
             lock,
             "The data can be n-dimensional, but the dimensions of the training data and of the test data should match."
         )
         .unwrap();
+        writeln!(
+            lock,
+            "A data filename of - reads that data from stdin, so only one of the data files can be -."
+        )
+        .unwrap();
+        writeln!(
+            lock,
+            "With the serde feature, the results can be printed as JSON rather than the default debug format."
+        )
+        .unwrap();
+        writeln!(
+            lock,
+            "If every test data point is labeled with a classification from the training data, the accuracy of each classification is also printed."
+        )
+        .unwrap();
+        writeln!(
+            lock,
+            "If an output file is given, the test data is written to it with the guessed classifications, one data point per line."
+        )
+        .unwrap();
 
         return;
     }
@@ -63,6 +93,38 @@ fn main() {
         return;
     }
 
+    // stdin can only be read once
+    if train_data_file_name == "-" && test_data_file_name == "-" {
+        println!("Error: only one data file can be read from stdin");
+        return;
+    }
+
+    // check output format
+    let json = match format.as_deref() {
+        None => false,
+        Some("json") if cfg!(feature = "serde") => true,
+        Some("json") => {
+            println!("Error: JSON output requires the serde feature");
+            return;
+        }
+        Some(_) => {
+            println!("Error: unknown output format");
+            return;
+        }
+    };
+
+    // check output data file name
+    if output_data_file_name.as_deref() == Some("") {
+        println!("Error: no output file specified");
+        return;
+    }
+
+    // the output file is data, so it cannot also be JSON
+    if json && output_data_file_name.is_some() {
+        println!("Error: JSON output cannot be combined with an output file");
+        return;
+    }
+
     // pull out learning rate
     let learning_rate = if let Some(arg) = args.get(3) {
         let Ok(learning_rate) = arg.parse::<f64>() else {
@@ -85,55 +147,98 @@ fn main() {
         0.0
     };
 
-    // parse training data
-    let train_data = {
-        // open and read file
-        let train_data_file = File::open(train_data_file_name);
-        let Ok(train_data_file_contents) = data_utils::io::read_file(train_data_file) else {
-            println!("Error: could not read training data");
+    // pull out random seed
+    let seed = if let Some(arg) = args.get(5) {
+        let Ok(seed) = arg.parse::<u64>() else {
+            println!("Error: invalid seed");
             return;
         };
+        Some(seed)
+    } else {
+        None
+    };
 
-        // map lines to DataPoints
-        let train_data: Result<Vec<_>, _> = train_data_file_contents
-            .lines()
-            .map(data_utils::DataPoint::<String>::try_from)
-            .collect();
-        let Ok(train_data) = train_data else {
-            println!("Error: could not parse training data");
-            return;
-        };
-        train_data
+    // parse training data
+    let train_data = {
+        // open and read file (or stdin)
+        let train_data_file_contents =
+            match data_utils::io::read_file_or_stdin(train_data_file_name) {
+                Ok(contents) => contents,
+                Err(e) => {
+                    println!("Error: could not read training data: {e}");
+                    return;
+                }
+            };
+
+        // map lines to DataPoints, reporting the line that could not be parsed
+        match data_utils::io::parse_data_points::<String>(&train_data_file_contents) {
+            Ok(train_data) => train_data,
+            Err(e) => {
+                println!("Error: could not parse training data: {e}");
+                return;
+            }
+        }
     };
 
     // parse test data
     let test_data = {
-        // open and read file
-        let test_data_file = File::open(test_data_file_name);
-        let Ok(test_data_file_contents) = data_utils::io::read_file(test_data_file) else {
-            println!("Error: could not read test data");
-            return;
+        // open and read file (or stdin)
+        let test_data_file_contents = match data_utils::io::read_file_or_stdin(test_data_file_name)
+        {
+            Ok(contents) => contents,
+            Err(e) => {
+                println!("Error: could not read test data: {e}");
+                return;
+            }
         };
 
-        // map lines to DataPoints
-        let test_data: Result<Vec<_>, _> = test_data_file_contents
-            .lines()
-            .map(data_utils::DataPoint::try_from)
-            .collect();
-        let Ok(test_data) = test_data else {
-            println!("Error: could not parse test data");
-            return;
-        };
-        test_data
+        // map lines to DataPoints, reporting the line that could not be parsed
+        match data_utils::io::parse_data_points::<String>(&test_data_file_contents) {
+            Ok(test_data) => test_data,
+            Err(e) => {
+                println!("Error: could not parse test data: {e}");
+                return;
+            }
+        }
     };
 
     // run single-layer perceptron algorithm
-    let test_res = data_utils::classify::single_layer_perceptron(
-        &train_data,
-        &test_data,
+    let config = PerceptronConfig {
         learning_rate,
         threshold,
-    );
-    // print the results (formatted)
-    println!("{test_res:#?}");
+        seed,
+        ..PerceptronConfig::default()
+    };
+    let test_res =
+        data_utils::classify::single_layer_perceptron_with_config(&train_data, &test_data, &config);
+
+    if let Some(output_data_file_name) = output_data_file_name {
+        // write the test data with the guessed classifications
+        let output_data: Vec<_> = test_res
+            .iter()
+            .map(|c| data_utils::DataPoint {
+                class: c.class_guess.clone(),
+                ..c.data.clone()
+            })
+            .collect();
+        if data_utils::io::write_data_points(output_data_file_name, &output_data).is_err() {
+            println!("Error: could not write output file");
+        }
+    } else if json {
+        // print the results as JSON
+        #[cfg(feature = "serde")]
+        println!("{}", serde_json::to_string_pretty(&test_res).unwrap());
+        return;
+    } else {
+        // print the results (formatted)
+        println!("{test_res:#?}");
+    }
+
+    // report the accuracy if the test data is labeled with the training classifications
+    if let Some(report) =
+        data_utils::classify::labeled_classification_report(&train_data, &test_res)
+    {
+        println!();
+        println!("{report}");
+    }
 }
@@ -0,0 +1,117 @@
+//! Tests running the binary Single-Layer Perceptron program.
+//!
+//! Author: Benjamin Hall
+
+use std::{
+    env, fs,
+    ops::Deref,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use data_utils::DataPoint;
+
+/// Gets the path of a data file in the root of the repository.
+fn data_path(name: &str) -> PathBuf {
+    [env!("CARGO_MANIFEST_DIR"), "..", "..", name]
+        .iter()
+        .collect()
+}
+
+/// Reads and parses a data file.
+fn read_data(path: &Path) -> Vec<DataPoint<String>> {
+    let contents = data_utils::io::read_file_from_path(path).unwrap();
+    data_utils::io::parse_data_points(&contents).unwrap()
+}
+
+/// A temporary file unique to this process, which is removed
+/// when dropped so it is cleaned up even if a test fails.
+struct TempFile(PathBuf);
+
+impl TempFile {
+    /// Gets a temporary file with the given name, without creating it.
+    fn new(name: &str) -> Self {
+        Self(env::temp_dir().join(format!(
+            "color_class_binary_slp_{}_{name}",
+            std::process::id()
+        )))
+    }
+}
+
+impl Deref for TempFile {
+    type Target = Path;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        // the file may not have been created
+        let _ = fs::remove_file(&self.0);
+    }
+}
+
+#[test]
+fn output_file() {
+    let train_path = data_path("color_training_binary.dat");
+    let test_path = data_path("color_test_binary.dat");
+    let output_path = TempFile::new("output.dat");
+
+    let status = Command::new(env!("CARGO_BIN_EXE_color_class_binary_slp"))
+        .args([&train_path, &test_path])
+        .arg("--output")
+        .arg(&*output_path)
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let output_data = read_data(&output_path);
+
+    // the output is the test data, each with one of the two classifications
+    let train_data = read_data(&train_path);
+    let test_data = read_data(&test_path);
+    assert_eq!(output_data.len(), test_data.len());
+    for (output, test) in output_data.iter().zip(&test_data) {
+        assert_eq!(output.point, test.point);
+        assert!(train_data.iter().any(|d| d.class == output.class));
+    }
+}
+
+#[test]
+fn seeded_runs() {
+    let train_path = data_path("color_training_binary.dat");
+    let test_path = data_path("color_test_binary.dat");
+    let run = |seed: &str| {
+        let output = Command::new(env!("CARGO_BIN_EXE_color_class_binary_slp"))
+            .args([&train_path, &test_path])
+            .args(["0.5", "0.0", seed])
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        String::from_utf8(output.stdout).unwrap()
+    };
+
+    // the same seed produces the same weights, and so the same confidences
+    let output = run("42");
+    assert!(output.contains("confidence"));
+    assert_eq!(run("42"), output);
+}
+
+#[test]
+fn missing_data() {
+    let train_path = data_path("color_training_binary.dat");
+    let missing_path = data_path("missing_test_binary.dat");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_color_class_binary_slp"))
+        .args([&train_path, &missing_path])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    // the error names the file that could not be read
+    let output = String::from_utf8(output.stdout).unwrap();
+    assert!(output.starts_with("Error: could not read test data: "));
+    assert!(output.contains("missing_test_binary.dat"));
+}
@@ -6,17 +6,26 @@
 
 use std::{
     env,
-    fs::File,
     io::{self, Write},
 };
 
+use data_utils::classify::Standardizer;
+
 /// Runs the k-nearest neighbor algorithm and outputs the results.
 ///
 /// Program input is the filename of the training data, the filename
 /// of the test data, and the number of neighbors used in the algorithm.
 fn main() {
     // get program arguments
-    let args: Vec<_> = env::args().collect();
+    let mut args: Vec<_> = env::args().collect();
+    // pull out output data file name, if given
+    let output_data_file_name = data_utils::args::take_option(&mut args, "--output");
+    // pull out output format, if given
+    let format = data_utils::args::take_option(&mut args, "--format");
+    // pull out standardize flag
+    let standardize = data_utils::args::take_flag(&mut args, "--standardize");
+    // pull out verbose flag
+    let verbose = data_utils::args::take_flag(&mut args, "--verbose");
     if args.len() != 4 {
         /* invalid number of arguments, print a help message */
         let mut lock = io::stdout().lock();
@@ -24,7 +33,7 @@ fn main() {
         writeln!(lock, "Author: Benjamin Hall").unwrap();
         writeln!(
             lock,
-            "Usage: ./color_class_knn [train data filename] [test data filename] [num neighbors]"
+            "Usage: ./color_class_knn [train data filename] [test data filename] [num neighbors] [--output output filename] [--format json] [--standardize] [--verbose]"
         )
         .unwrap();
         writeln!(lock).unwrap();
@@ -39,6 +48,46 @@ fn main() {
             "The data can be n-dimensional, but the dimensions of the training data and of the test data should match."
         )
         .unwrap();
+        writeln!(
+            lock,
+            "A data filename of - reads that data from stdin, so only one of the data files can be -."
+        )
+        .unwrap();
+        writeln!(
+            lock,
+            "If standardize is given, each dimension is scaled to zero mean and unit standard deviation, so dimensions with larger ranges do not dominate the distances."
+        )
+        .unwrap();
+        writeln!(
+            lock,
+            "The mean and standard deviation are calculated from the training data, and the same values are applied to both the training data and the test data."
+        )
+        .unwrap();
+        writeln!(
+            lock,
+            "If verbose is given, the nearest neighbors of each test data point are also printed with their distances and classifications."
+        )
+        .unwrap();
+        writeln!(
+            lock,
+            "The data points are printed as given, but with standardize, the distances are in standardized units."
+        )
+        .unwrap();
+        writeln!(
+            lock,
+            "With the serde feature, the results can be printed as JSON rather than the default debug format, without the nearest neighbors."
+        )
+        .unwrap();
+        writeln!(
+            lock,
+            "If every test data point is labeled with a classification from the training data, the accuracy of each classification is also printed."
+        )
+        .unwrap();
+        writeln!(
+            lock,
+            "If an output file is given, the test data is written to it with the guessed classifications, one data point per line."
+        )
+        .unwrap();
 
         return;
     }
@@ -57,6 +106,44 @@ fn main() {
         return;
     }
 
+    // stdin can only be read once
+    if train_data_file_name == "-" && test_data_file_name == "-" {
+        println!("Error: only one data file can be read from stdin");
+        return;
+    }
+
+    // check output format
+    let json = match format.as_deref() {
+        None => false,
+        Some("json") if cfg!(feature = "serde") => true,
+        Some("json") => {
+            println!("Error: JSON output requires the serde feature");
+            return;
+        }
+        Some(_) => {
+            println!("Error: unknown output format");
+            return;
+        }
+    };
+
+    // check output data file name
+    if output_data_file_name.as_deref() == Some("") {
+        println!("Error: no output file specified");
+        return;
+    }
+
+    // the output file is data, so it cannot also be JSON
+    if json && output_data_file_name.is_some() {
+        println!("Error: JSON output cannot be combined with an output file");
+        return;
+    }
+
+    // the nearest neighbors are not part of the JSON results
+    if json && verbose {
+        println!("Error: JSON output cannot be combined with verbose output");
+        return;
+    }
+
     // pull out number of nearest neighbors
     let Ok(num_neighbors) = args[3].parse::<usize>() else {
         println!("Error: invalid number of nearest neighbors");
@@ -65,48 +152,112 @@ fn main() {
 
     // parse training data
     let train_data = {
-        // open and read file
-        let train_data_file = File::open(train_data_file_name);
-        let Ok(train_data_file_contents) = data_utils::io::read_file(train_data_file) else {
-            println!("Error: could not read training data");
-            return;
-        };
+        // open and read file (or stdin)
+        let train_data_file_contents =
+            match data_utils::io::read_file_or_stdin(train_data_file_name) {
+                Ok(contents) => contents,
+                Err(e) => {
+                    println!("Error: could not read training data: {e}");
+                    return;
+                }
+            };
 
-        // map lines to DataPoints
-        let train_data: Result<Vec<_>, _> = train_data_file_contents
-            .lines()
-            .map(data_utils::DataPoint::<String>::try_from)
-            .collect();
-        let Ok(train_data) = train_data else {
-            println!("Error: could not parse training data");
-            return;
-        };
-        train_data
+        // map lines to DataPoints, reporting the line that could not be parsed
+        match data_utils::io::parse_data_points::<String>(&train_data_file_contents) {
+            Ok(train_data) => train_data,
+            Err(e) => {
+                println!("Error: could not parse training data: {e}");
+                return;
+            }
+        }
     };
 
     // parse test data
     let test_data = {
-        // open and read file
-        let test_data_file = File::open(test_data_file_name);
-        let Ok(test_data_file_contents) = data_utils::io::read_file(test_data_file) else {
-            println!("Error: could not read test data");
-            return;
+        // open and read file (or stdin)
+        let test_data_file_contents = match data_utils::io::read_file_or_stdin(test_data_file_name)
+        {
+            Ok(contents) => contents,
+            Err(e) => {
+                println!("Error: could not read test data: {e}");
+                return;
+            }
         };
 
-        // map lines to DataPoints
-        let test_data: Result<Vec<_>, _> = test_data_file_contents
-            .lines()
-            .map(data_utils::DataPoint::try_from)
-            .collect();
-        let Ok(test_data) = test_data else {
-            println!("Error: could not parse test data");
-            return;
-        };
-        test_data
+        // map lines to DataPoints, reporting the line that could not be parsed
+        match data_utils::io::parse_data_points::<String>(&test_data_file_contents) {
+            Ok(test_data) => test_data,
+            Err(e) => {
+                println!("Error: could not parse test data: {e}");
+                return;
+            }
+        }
     };
 
+    // standardize the data using the parameters of the training data
+    let standardized_data = standardize.then(|| {
+        let (standardizer, train_data) = Standardizer::fit_transform(&train_data);
+        let test_data: Vec<_> = test_data
+            .iter()
+            .map(|d| standardizer.transform_point(d))
+            .collect();
+        (train_data, test_data)
+    });
+    let (knn_train_data, knn_test_data) = standardized_data
+        .as_ref()
+        .map_or((&train_data, &test_data), |(train, test)| (train, test));
+
     // run k-nearest neighbor algorithm
-    let test_res = data_utils::classify::k_nearest_neighbor(&train_data, &test_data, num_neighbors);
-    // print the results (formatted)
-    println!("{test_res:#?}");
+    let test_res =
+        data_utils::classify::k_nearest_neighbor(knn_train_data, knn_test_data, num_neighbors);
+
+    if let Some(output_data_file_name) = output_data_file_name {
+        // write the (unstandardized) test data with the guessed classifications
+        let output_data: Vec<_> = test_data
+            .iter()
+            .zip(&test_res)
+            .map(|(d, c)| data_utils::DataPoint {
+                class: c.class_guess.clone(),
+                ..d.clone()
+            })
+            .collect();
+        if data_utils::io::write_data_points(output_data_file_name, &output_data).is_err() {
+            println!("Error: could not write output file");
+        }
+    } else if json {
+        // print the results as JSON
+        #[cfg(feature = "serde")]
+        println!("{}", serde_json::to_string_pretty(&test_res).unwrap());
+        return;
+    } else {
+        // print the results (formatted)
+        println!("{test_res:#?}");
+    }
+
+    if verbose {
+        // print the neighbors that voted on each classification
+        let neighbors =
+            data_utils::classify::nearest_neighbors(knn_train_data, knn_test_data, num_neighbors);
+        for ((n, d), c) in neighbors.iter().zip(&test_data).zip(&test_res) {
+            println!();
+            // print the (unstandardized) test data point
+            println!("{d} (guess: {})", c.class_guess);
+            for neighbor in &n.neighbors {
+                // find the (unstandardized) training data point of the neighbor
+                let i = knn_train_data
+                    .iter()
+                    .position(|t| std::ptr::eq(t, neighbor.data))
+                    .unwrap();
+                println!("  distance {:.4}: {}", neighbor.dist, train_data[i]);
+            }
+        }
+    }
+
+    // report the accuracy if the test data is labeled with the training classifications
+    if let Some(report) =
+        data_utils::classify::labeled_classification_report(&train_data, &test_res)
+    {
+        println!();
+        println!("{report}");
+    }
 }
@@ -0,0 +1,264 @@
+//! Tests running the k-nearest neighbor program.
+//!
+//! Author: Benjamin Hall
+
+use std::{
+    env, fs,
+    io::Write,
+    ops::Deref,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+};
+
+use data_utils::DataPoint;
+
+/// Gets the path of a data file in the root of the repository.
+fn data_path(name: &str) -> PathBuf {
+    [env!("CARGO_MANIFEST_DIR"), "..", "..", name]
+        .iter()
+        .collect()
+}
+
+/// Reads and parses a data file.
+fn read_data(path: &Path) -> Vec<DataPoint<String>> {
+    let contents = data_utils::io::read_file_from_path(path).unwrap();
+    data_utils::io::parse_data_points(&contents).unwrap()
+}
+
+/// A temporary file unique to this process, which is removed
+/// when dropped so it is cleaned up even if a test fails.
+struct TempFile(PathBuf);
+
+impl TempFile {
+    /// Gets a temporary file with the given name, without creating it.
+    fn new(name: &str) -> Self {
+        Self(env::temp_dir().join(format!("color_class_knn_{}_{name}", std::process::id())))
+    }
+}
+
+impl Deref for TempFile {
+    type Target = Path;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        // the file may not have been created
+        let _ = fs::remove_file(&self.0);
+    }
+}
+
+/// Writes a temporary data file unique to this process.
+fn write_temp(name: &str, contents: &str) -> TempFile {
+    let file = TempFile::new(name);
+    fs::write(&*file, contents).unwrap();
+    file
+}
+
+/// Runs the program on the given data files with
+/// the given options, returning its output.
+fn run(train_path: &Path, test_path: &Path, options: &[&str]) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_color_class_knn"))
+        .args([train_path, test_path])
+        .arg("1")
+        .args(options)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn output_file() {
+    let train_path = data_path("color_training_full.dat");
+    let test_path = data_path("color_test_full.dat");
+    let output_path = TempFile::new("output.dat");
+
+    let status = Command::new(env!("CARGO_BIN_EXE_color_class_knn"))
+        .args([&train_path, &test_path])
+        .arg("4")
+        .arg("--output")
+        .arg(&*output_path)
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let output_data = read_data(&output_path);
+
+    // the output is the test data with the guessed classifications,
+    // where an even number of neighbors may tie
+    let train_data = read_data(&train_path);
+    let test_data = read_data(&test_path);
+    let expected: Vec<_> = data_utils::classify::k_nearest_neighbor(&train_data, &test_data, 4)
+        .iter()
+        .map(|c| (c.data.point.clone(), c.class_guess.clone()))
+        .collect();
+    let output: Vec<_> = output_data
+        .into_iter()
+        .map(|d| (d.point, d.class))
+        .collect();
+    assert_eq!(output, expected);
+}
+
+#[test]
+fn accuracy_report() {
+    let train_path = write_temp("train.dat", "0 0 red\n1 1 red\n10 10 blue\n11 11 blue\n");
+    // three of the four test data points are classified correctly
+    let test_path = write_temp(
+        "test.dat",
+        "0.5 0.5 red\n10.5 10.5 blue\n9 9 blue\n2 2 blue\n",
+    );
+    let unlabeled_path = write_temp("unlabeled.dat", "0.5 0.5 ?\n10.5 10.5 ?\n");
+
+    let output = run(&train_path, &test_path, &[]);
+    let unlabeled_output = run(&train_path, &unlabeled_path, &[]);
+
+    let accuracy_line = output.lines().last().unwrap();
+    assert!(accuracy_line.trim_start().starts_with("accuracy"));
+    assert!(accuracy_line.contains("0.75"));
+    assert!(output.contains("precision"));
+
+    // unlabeled test data has no accuracy to report
+    assert!(!unlabeled_output.contains("accuracy"));
+}
+
+#[test]
+fn standardize() {
+    // the classification depends on the first dimension, which
+    // has a much smaller range than the second dimension
+    let train_path = write_temp(
+        "scaled_train.dat",
+        "0.1 0 red\n0.2 1000 red\n0.8 300 blue\n0.9 700 blue\n",
+    );
+    let test_path = write_temp(
+        "scaled_test.dat",
+        "0.15 320 red\n0.15 680 red\n0.85 20 blue\n0.85 980 blue\n",
+    );
+
+    let output = run(&train_path, &test_path, &[]);
+    let standardized_output = run(&train_path, &test_path, &["--standardize"]);
+
+    // unscaled, the second dimension dominates the distances
+    assert!(output.lines().last().unwrap().contains("0.00"));
+    assert!(standardized_output.lines().last().unwrap().contains("1.00"));
+}
+
+#[test]
+fn verbose() {
+    let train_path = write_temp("verbose_train.dat", "0 0 red\n3 4 blue\n");
+    let test_path = write_temp("verbose_test.dat", "0 0 red\n");
+
+    let output = run(&train_path, &test_path, &[]);
+    let verbose_output = run(&train_path, &test_path, &["--verbose"]);
+
+    // the default output has no neighbors
+    assert!(!output.contains("distance"));
+    assert!(verbose_output.contains("red (guess: red)"));
+    assert!(verbose_output.contains("  distance 0.0000: "));
+    // the neighbor at 5.0 does not vote with a single neighbor
+    assert!(!verbose_output.contains("distance 5.0000"));
+}
+
+#[test]
+fn verbose_standardize() {
+    let train_path = write_temp(
+        "verbose_scaled_train.dat",
+        "0 0 red
+10 100 blue
+",
+    );
+    let test_path = write_temp(
+        "verbose_scaled_test.dat",
+        "9 90 blue
+",
+    );
+
+    let output = run(&train_path, &test_path, &["--standardize", "--verbose"]);
+
+    // the points are printed as given, not standardized
+    let test_point: DataPoint<String> = "9 90 blue".parse().unwrap();
+    let train_point: DataPoint<String> = "10 100 blue".parse().unwrap();
+    assert!(output.contains(&format!("{test_point} (guess: blue)")));
+    assert!(output.contains(&format!(": {train_point}")));
+    // the distance is in standardized units, (0.1, 0.1) scaled by 1 / 5 and 1 / 50
+    assert!(output.contains("  distance 0.2828: "));
+}
+
+#[test]
+fn stdin_data() {
+    let train_path = data_path("color_training_full.dat");
+    let test_path = data_path("color_test_full.dat");
+    let test_contents = fs::read_to_string(&test_path).unwrap();
+
+    // pipe the test data into the program
+    let mut child = Command::new(env!("CARGO_BIN_EXE_color_class_knn"))
+        .arg(&train_path)
+        .arg("-")
+        .arg("1")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(test_contents.as_bytes())
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success());
+
+    // the results match reading the test data from its file
+    let output = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(output, run(&train_path, &test_path, &[]));
+    assert!(output.contains("accuracy"));
+}
+
+#[test]
+fn parse_error() {
+    let train_path = write_temp("bad_train.dat", "0 0 red\n1 x blue\n");
+    let test_path = data_path("color_test_full.dat");
+
+    let output = run(&train_path, &test_path, &[]);
+
+    // the error names the line that could not be parsed
+    assert!(output.starts_with("Error: could not parse training data: line 2:"));
+    assert!(output.contains("1 x blue"));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn json_format() {
+    let train_path = data_path("color_training_full.dat");
+    let test_path = data_path("color_test_full.dat");
+
+    let output = run(&train_path, &test_path, &["--format", "json"]);
+    let results: serde_json::Value = serde_json::from_str(&output).unwrap();
+
+    let results = results.as_array().unwrap();
+    assert_eq!(results.len(), read_data(&test_path).len());
+    for result in results {
+        for field in ["point", "class", "class_guess", "confidence"] {
+            assert!(result.get(field).is_some());
+        }
+    }
+    assert_eq!(results[0]["class"], "red");
+
+    // JSON is not written to an output file
+    let output_path = TempFile::new("json_output.dat");
+    let output_arg = output_path.to_str().unwrap();
+    let output = run(
+        &train_path,
+        &test_path,
+        &["--format", "json", "--output", output_arg],
+    );
+    assert!(output.starts_with("Error: JSON output cannot be combined with an output file"));
+    assert!(!output_path.exists());
+
+    // the nearest neighbors are not part of the JSON results
+    let output = run(&train_path, &test_path, &["--format", "json", "--verbose"]);
+    assert!(output.starts_with("Error: JSON output cannot be combined with verbose output"));
+}
@@ -0,0 +1,64 @@
+//! This module provides interfaces to pull
+//! options out of program arguments.
+//!
+//! Author: Benjamin Hall
+
+/// Removes an option and its value from the program arguments,
+/// returning the value if the option is present. The value is
+/// empty if the option is the last argument.
+///
+/// # Examples
+///
+/// Basic usage:
+/// ```
+/// # use data_utils::args::take_option;
+/// let mut args = vec![String::from("a"), String::from("--output"), String::from("b")];
+/// assert_eq!(take_option(&mut args, "--output").as_deref(), Some("b"));
+/// assert_eq!(args, ["a"]);
+/// assert_eq!(take_option(&mut args, "--output"), None);
+/// ```
+pub fn take_option(args: &mut Vec<String>, option: &str) -> Option<String> {
+    let i = args.iter().position(|arg| arg == option)?;
+    args.remove(i);
+    Some(if i < args.len() {
+        args.remove(i)
+    } else {
+        String::new()
+    })
+}
+
+/// Removes a flag from the program arguments, returning
+/// whether the flag is present.
+pub fn take_flag(args: &mut Vec<String>, flag: &str) -> bool {
+    let len = args.len();
+    args.retain(|arg| arg != flag);
+    args.len() != len
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Converts string slices to program arguments.
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|&arg| arg.to_owned()).collect()
+    }
+
+    #[test]
+    fn options() {
+        let mut a = args(&["prog", "--format", "json", "train.dat", "--output"]);
+        assert_eq!(take_option(&mut a, "--format").as_deref(), Some("json"));
+        // the last option has an empty value
+        assert_eq!(take_option(&mut a, "--output").as_deref(), Some(""));
+        assert_eq!(take_option(&mut a, "--cv"), None);
+        assert_eq!(a, ["prog", "train.dat"]);
+    }
+
+    #[test]
+    fn flags() {
+        let mut a = args(&["prog", "--verbose", "train.dat"]);
+        assert!(take_flag(&mut a, "--verbose"));
+        assert!(!take_flag(&mut a, "--verbose"));
+        assert_eq!(a, ["prog", "train.dat"]);
+    }
+}
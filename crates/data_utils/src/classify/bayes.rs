@@ -3,12 +3,49 @@
 //!
 //! Author: Benjamin Hall
 
-use super::Classification;
-use crate::{Complex, DataPoint, Point};
+use super::{max_softmax, softmax, Classification, Classifier};
+use crate::{
+    dataset::{group_by_class, weighted_mean},
+    lin_alg::Matrix,
+    Complex, DataPoint, Point,
+};
 use std::collections::BTreeMap;
 
+/// The prior probabilities of each classification
+/// used by the Bayesian plug-in rule.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum Priors<T> {
+    /// Every classification is equally likely
+    #[default]
+    Equal,
+    /// Each classification's prior is its frequency in the training data
+    Estimated,
+    /// Each classification's prior is given explicitly. Any classification
+    /// without a prior has a prior of zero, so it is never chosen.
+    Given(BTreeMap<T, f64>),
+}
+
+/// The posterior probability of each classification.
+pub type Posteriors<T> = Vec<(T, f64)>;
+
+/// Groups the given training data by classification,
+/// with the classifications in sorted order.
+fn sorted_groups<T>(data: &[DataPoint<T>]) -> Vec<(&T, Vec<&DataPoint<T>>)>
+where
+    T: Ord,
+{
+    let mut groups = group_by_class(data);
+    groups.sort_by(|a, b| a.0.cmp(b.0));
+    groups
+}
+
 /// Runs the Bayesian plug-in rule with the given training data
 /// on the given test data.
+///
+/// Every classification is assumed to be equally likely. If the
+/// training data only has one classification, every test data
+/// point is given that classification.
+#[inline]
 #[must_use]
 pub fn bayes_plug_in<'a, T>(
     train_data: &[DataPoint<T>],
@@ -17,66 +54,764 @@ pub fn bayes_plug_in<'a, T>(
 where
     T: Clone + Default + Ord,
 {
-    /// Stores plug-in weights for a classification.
-    struct Weights {
-        /// w = 2µ
-        w: Point,
-        /// w_0 = µ⋅µ
-        w_0: Complex,
-    }
+    bayes_plug_in_with_priors(train_data, test_data, &Priors::Equal)
+}
 
-    // group training data by classification
-    let mut train_data_grp: BTreeMap<&T, Vec<&Point>> = BTreeMap::new();
-    for d in train_data {
-        if let Some(g) = train_data_grp.get_mut(&d.class) {
-            g.push(&d.point);
-        } else {
-            train_data_grp.insert(&d.class, vec![&d.point]);
+/// Stores plug-in weights for a classification.
+struct Weights {
+    /// w = 2µ
+    w: Point,
+    /// w_0 = µ⋅µ - 2 ln P
+    w_0: Complex,
+}
+
+impl Weights {
+    /// Calculates the plug-in weights for a classification with
+    /// the given mean and log prior probability.
+    fn new(mean: &Point, ln_prior: f64) -> Self {
+        // create a point with the conjugates of all complex numbers
+        let mut mean_conj = mean.clone();
+        for m in &mut mean_conj.0 {
+            *m = m.conjugate();
+        }
+
+        // dot the mean with its conjugate, offset by the prior
+        let w_0 = mean.dot(&mean_conj) - Complex::from(2.0 * ln_prior);
+        Self {
+            // conjugate mean for weight offset
+            w: mean_conj.scale(2.0),
+            w_0,
         }
     }
+}
+
+/// Calculates the plug-in weights for each classification
+/// in the given training data.
+fn plug_in_weights<'a, T>(
+    train_data: &'a [DataPoint<T>],
+    priors: &Priors<T>,
+) -> Vec<(&'a T, Weights)>
+where
+    T: Ord,
+{
+    let train_weight: f64 = train_data.iter().map(|d| d.weight).sum();
+
+    // group training data by classification
+    let train_data_grp = sorted_groups(train_data);
 
-    // calculate means for each classification
+    // calculate means and log priors for each classification
     let train_data_means = train_data_grp.into_iter().map(|(class, points)| {
-        let cnt = points.len() as f64;
-        // sum together points and scale by 1/cnt
-        let mean = points.into_iter().sum::<Point>().scale(cnt.recip());
-        (class, mean)
+        let ln_prior = match priors {
+            // equal priors do not affect the result
+            Priors::Equal => 0.0,
+            Priors::Estimated => {
+                let total: f64 = points.iter().map(|d| d.weight).sum();
+                (total / train_weight).ln()
+            }
+            Priors::Given(priors) => priors.get(class).map_or(f64::NEG_INFINITY, |p| p.ln()),
+        };
+        let mean = weighted_mean(points.iter().map(|d| (&d.point, d.weight)));
+        (class, mean, ln_prior)
     });
 
     // calculate the two weight components for each classification
-    let train_data_weights: Vec<_> = train_data_means
-        .map(|(class, mean)| {
-            // create a point with the conjugates of all complex numbers
-            let mut mean_conj = mean.clone();
-            for m in &mut mean_conj.0 {
-                *m = m.conjugate();
-            }
+    train_data_means
+        .map(|(class, mean, ln_prior)| (class, Weights::new(&mean, ln_prior)))
+        .collect()
+}
 
-            // dot the mean with its conjugate
-            let w_0 = mean.dot(&mean_conj);
-            let weights = Weights {
-                // conjugate mean for weight offset
-                w: mean_conj.scale(2.0),
-                w_0,
-            };
-            (class, weights)
+/// Runs the plug-in rule on a point for all classifications.
+#[inline]
+fn plug_in_scores<'a, 'b, T>(
+    weights: &'b [(&'a T, Weights)],
+    point: &'b Point,
+) -> impl Iterator<Item = (&'a T, f64)> + 'b {
+    weights
+        .iter()
+        .map(move |(class, weights)| (*class, (weights.w.dot(point) - weights.w_0).re))
+}
+
+/// Picks the classification with the maximum discriminant, returning it
+/// with its posterior probability. Each discriminant is twice the
+/// log-likelihood of its classification, offset by a shared constant.
+#[inline]
+fn max_posterior<'a, T>(class_results: impl IntoIterator<Item = (&'a T, f64)>) -> (T, Option<f64>)
+where
+    T: 'a + Clone + Default,
+{
+    max_softmax(
+        class_results
+            .into_iter()
+            .map(|(class, score)| (class, score / 2.0)),
+    )
+}
+
+/// Runs the Bayesian plug-in rule with the given training data
+/// on the given test data, using the given prior probabilities
+/// of each classification.
+#[must_use]
+pub fn bayes_plug_in_with_priors<'a, T>(
+    train_data: &[DataPoint<T>],
+    test_data: &'a [DataPoint<T>],
+    priors: &Priors<T>,
+) -> Vec<Classification<'a, T>>
+where
+    T: Clone + Default + Ord,
+{
+    let train_data_weights = plug_in_weights(train_data, priors);
+
+    // run Bayesian plug-in rule on all test data and collect the results
+    test_data
+        .iter()
+        .map(|data| {
+            // run the plug-in rule on this data for all classifications
+            let class_results = plug_in_scores(&train_data_weights, &data.point);
+            // find the maximum classification value, pull out class and its posterior
+            let (class_guess, confidence) = max_posterior(class_results);
+            // wrap in a Classification
+            Classification {
+                data,
+                class_guess,
+                confidence,
+            }
         })
-        .collect();
+        .collect()
+}
+
+/// Runs the Bayesian plug-in rule with the given training data
+/// on the given test data, using the given prior probabilities
+/// of each classification.
+///
+/// Alongside each classification, this returns the posterior
+/// probability of each classification, in the order of the
+/// classifications. The posteriors are the softmax of the
+/// plug-in rule's log-likelihoods, calculated in log space
+/// so that distant data points do not overflow.
+#[must_use]
+pub fn bayes_plug_in_posteriors<'a, T>(
+    train_data: &[DataPoint<T>],
+    test_data: &'a [DataPoint<T>],
+    priors: &Priors<T>,
+) -> Vec<(Classification<'a, T>, Posteriors<T>)>
+where
+    T: Clone + Default + Ord,
+{
+    let train_data_weights = plug_in_weights(train_data, priors);
 
     // run Bayesian plug-in rule on all test data and collect the results
     test_data
         .iter()
         .map(|data| {
             // run the plug-in rule on this data for all classifications
-            let class_results = train_data_weights
-                .iter()
-                .map(|(class, weights)| (*class, weights.w.dot(&data.point) - weights.w_0));
+            let class_results: Vec<_> = plug_in_scores(&train_data_weights, &data.point).collect();
             // find the maximum classification value, pull out class
             let class_guess = class_results
-                .max_by(|a, b| a.1.re.total_cmp(&b.1.re))
-                .map_or_else(T::default, |(class, _)| class.clone());
+                .iter()
+                .max_by(|a, b| a.1.total_cmp(&b.1))
+                .map_or_else(T::default, |(class, _)| (*class).clone());
+
+            // the plug-in rule is twice the log-likelihood, offset by a constant
+            let log_likelihoods: Vec<_> =
+                class_results.iter().map(|(_, score)| score / 2.0).collect();
+            // normalize the likelihoods so they sum to 1
+            let posteriors: Posteriors<T> = class_results
+                .iter()
+                .zip(softmax(&log_likelihoods))
+                .map(|((class, _), p)| ((*class).clone(), p))
+                .collect();
+
+            // the posterior of the guess is the confidence
+            let confidence = posteriors
+                .iter()
+                .find(|(class, _)| *class == class_guess)
+                .map(|&(_, p)| p);
+
+            // wrap in a Classification
+            let classification = Classification {
+                data,
+                class_guess,
+                confidence,
+            };
+            (classification, posteriors)
+        })
+        .collect()
+}
+
+/// Runs the Bayesian plug-in rule with the given training data on the
+/// given test data, modeling each classification as a Gaussian
+/// distribution with its own full covariance matrix.
+///
+/// This uses the quadratic discriminant
+/// `g(x) = -(x - µ)ᴴ Σ⁻¹ (x - µ) - ln|Σ|`, so it can handle
+/// classifications with elongated or differently-shaped distributions.
+///
+/// # Panics
+///
+/// Panics if the covariance matrix of any classification is singular,
+/// such as when a classification has fewer data points than dimensions.
+/// Use [`bayes_plug_in_regularized`] to handle these classifications.
+#[inline]
+#[must_use]
+pub fn bayes_plug_in_gaussian<'a, T>(
+    train_data: &[DataPoint<T>],
+    test_data: &'a [DataPoint<T>],
+) -> Vec<Classification<'a, T>>
+where
+    T: Clone + Default + Ord,
+{
+    bayes_plug_in_regularized(train_data, test_data, 0.0)
+}
+
+/// Runs the Bayesian plug-in rule with the given training data on the
+/// given test data, modeling each classification as a Gaussian
+/// distribution with its own regularized covariance matrix.
+///
+/// Each covariance matrix is shrunk towards a scaled identity matrix,
+/// `(1 - λ)Σ + λσ²I`, where `λ` is the `shrinkage` in the range 0.0..=1.0
+/// and `σ²` is the mean variance of the classification's dimensions.
+/// This keeps the covariance matrix invertible for classifications
+/// with few data points, i.e. regularized discriminant analysis.
+///
+/// # Panics
+///
+/// Panics if the regularized covariance matrix of any classification
+/// is singular, such as when the shrinkage is 0.0 and a classification
+/// has fewer data points than dimensions.
+#[must_use]
+pub fn bayes_plug_in_regularized<'a, T>(
+    train_data: &[DataPoint<T>],
+    test_data: &'a [DataPoint<T>],
+    shrinkage: f64,
+) -> Vec<Classification<'a, T>>
+where
+    T: Clone + Default + Ord,
+{
+    /// Stores the Gaussian parameters of a classification.
+    struct Gaussian {
+        /// µ
+        mean: Point,
+        /// Σ⁻¹
+        cov_inv: Matrix,
+        /// ln|Σ|
+        ln_det: f64,
+    }
+
+    // group training data by classification
+    let train_data_grp = sorted_groups(train_data);
+
+    // calculate the mean and covariance for each classification
+    let train_data_gaussians: Vec<_> = train_data_grp
+        .into_iter()
+        .map(|(class, points)| {
+            let mean = weighted_mean(points.iter().map(|d| (&d.point, d.weight)));
+            let dim = mean.0.len();
+
+            // Σ = 1/∑w ∑ w(x - µ)(x - µ)ᴴ
+            let mut cov = Matrix::zeros(dim, dim);
+            for d in &points {
+                let offset = &d.point - &mean;
+                for (i, &x_i) in offset.0.iter().enumerate() {
+                    for (c, &x_j) in cov[i].iter_mut().zip(&offset.0) {
+                        *c += (x_i * x_j.conjugate()).scale(d.weight);
+                    }
+                }
+            }
+            let total: f64 = points.iter().map(|d| d.weight).sum();
+            for i in 0..dim {
+                for c in &mut cov[i] {
+                    *c = c.scale(total.recip());
+                }
+            }
+
+            if shrinkage != 0.0 {
+                // σ² = tr(Σ) / d, use the identity if there is no variance
+                let trace: f64 = (0..dim).map(|i| cov[i][i].re).sum();
+                let variance = if trace > 0.0 { trace / dim as f64 } else { 1.0 };
+                // (1 - λ)Σ + λσ²I
+                for i in 0..dim {
+                    for c in &mut cov[i] {
+                        *c = c.scale(1.0 - shrinkage);
+                    }
+                    cov[i][i] += Complex::from(shrinkage * variance);
+                }
+            }
+
+            let (cov_inv, det) = cov
+                .inverse_with_determinant()
+                .expect("The covariance matrix of a classification is singular");
+            let gaussian = Gaussian {
+                mean,
+                cov_inv,
+                ln_det: det.re.hypot(det.im).ln(),
+            };
+            (class, gaussian)
+        })
+        .collect();
+
+    // run Bayesian plug-in rule on all test data and collect the results
+    test_data
+        .iter()
+        .map(|data| {
+            // run the quadratic discriminant on this data for all classifications
+            let class_results = train_data_gaussians.iter().map(|(class, gaussian)| {
+                let offset = &data.point - &gaussian.mean;
+                // (x - µ)ᴴ Σ⁻¹ (x - µ)
+                let mahalanobis: Complex = offset
+                    .0
+                    .iter()
+                    .zip((&gaussian.cov_inv * &offset).0)
+                    .map(|(x_i, row_dot)| x_i.conjugate() * row_dot)
+                    .sum();
+                (*class, -mahalanobis.re - gaussian.ln_det)
+            });
+            // find the maximum classification value, pull out class and its posterior
+            let (class_guess, confidence) = max_posterior(class_results);
+            // wrap in a Classification
+            Classification {
+                data,
+                class_guess,
+                confidence,
+            }
+        })
+        .collect()
+}
+
+/// Runs the naive Bayes rule with the given training data on the given
+/// test data, modeling each classification as a Gaussian distribution
+/// with a diagonal covariance matrix.
+///
+/// This assumes the dimensions of the data are independent, so only the
+/// variance of each dimension is estimated. This uses the discriminant
+/// `g(x) = -∑ |xᵢ - µᵢ|² / σᵢ² - ∑ ln σᵢ²`. Dimensions with no variance
+/// are given a small variance to avoid dividing by zero.
+#[must_use]
+pub fn bayes_naive<'a, T>(
+    train_data: &[DataPoint<T>],
+    test_data: &'a [DataPoint<T>],
+) -> Vec<Classification<'a, T>>
+where
+    T: Clone + Default + Ord,
+{
+    /// The minimum variance of a dimension.
+    const MIN_VARIANCE: f64 = 1e-9;
+
+    /// Stores the diagonal Gaussian parameters of a classification.
+    struct Gaussian {
+        /// µ
+        mean: Point,
+        /// σᵢ²
+        variance: Vec<f64>,
+        /// ∑ ln σᵢ²
+        ln_det: f64,
+    }
+
+    // group training data by classification
+    let train_data_grp = sorted_groups(train_data);
+
+    // calculate the mean and variance of each dimension for each classification
+    let train_data_gaussians: Vec<_> = train_data_grp
+        .into_iter()
+        .map(|(class, points)| {
+            let mean = weighted_mean(points.iter().map(|d| (&d.point, d.weight)));
+
+            // σᵢ² = 1/∑w ∑ w|xᵢ - µᵢ|²
+            let mut variance = vec![0.0; mean.0.len()];
+            for d in &points {
+                let offset = &d.point - &mean;
+                for (v, x) in variance.iter_mut().zip(&offset.0) {
+                    *v += d.weight * (*x * x.conjugate()).re;
+                }
+            }
+            let total: f64 = points.iter().map(|d| d.weight).sum();
+            for v in &mut variance {
+                *v = (*v / total).max(MIN_VARIANCE);
+            }
+
+            let ln_det = variance.iter().map(|v| v.ln()).sum();
+            let gaussian = Gaussian {
+                mean,
+                variance,
+                ln_det,
+            };
+            (class, gaussian)
+        })
+        .collect();
+
+    // run naive Bayes rule on all test data and collect the results
+    test_data
+        .iter()
+        .map(|data| {
+            // run the discriminant on this data for all classifications
+            let class_results = train_data_gaussians.iter().map(|(class, gaussian)| {
+                let offset = &data.point - &gaussian.mean;
+                // ∑ |xᵢ - µᵢ|² / σᵢ²
+                let dist: f64 = offset
+                    .0
+                    .iter()
+                    .zip(&gaussian.variance)
+                    .map(|(x, v)| (*x * x.conjugate()).re / v)
+                    .sum();
+                (*class, -dist - gaussian.ln_det)
+            });
+            // find the maximum classification value, pull out class and its posterior
+            let (class_guess, confidence) = max_posterior(class_results);
             // wrap in a Classification
-            Classification { data, class_guess }
+            Classification {
+                data,
+                class_guess,
+                confidence,
+            }
         })
         .collect()
 }
+
+/// A Bayesian plug-in rule model that can be trained incrementally.
+///
+/// The model keeps a running total weight and mean of each classification,
+/// so new training data can be added without revisiting old data.
+/// Every classification is assumed to be equally likely.
+#[derive(Clone, Debug, Default)]
+pub struct BayesModel<T> {
+    /// The total sample weight and the mean of each classification
+    classes: BTreeMap<T, (f64, Point)>,
+}
+
+impl<T> BayesModel<T>
+where
+    T: Clone + Default + Ord,
+{
+    /// Creates an untrained model.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            classes: BTreeMap::new(),
+        }
+    }
+
+    /// Creates a model trained on the given data.
+    #[must_use]
+    pub fn fit(train_data: &[DataPoint<T>]) -> Self {
+        let mut model = Self::new();
+        for d in train_data {
+            model.update(d);
+        }
+        model
+    }
+
+    /// Adds a data point to the model, updating the mean of its classification.
+    pub fn update(&mut self, data: &DataPoint<T>) {
+        let (total, mean) = self.classes.entry(data.class.clone()).or_default();
+        *total += data.weight;
+        // µ_n = µ_(n-1) + w(x - µ_(n-1)) / ∑w, the zero point while ∑w = 0
+        let ratio = if *total == 0.0 {
+            0.0
+        } else {
+            data.weight / *total
+        };
+        let offset = &data.point - &*mean;
+        *mean += offset.scale(ratio);
+    }
+
+    /// Returns the mean of the given classification,
+    /// if the model has been trained on it.
+    #[inline]
+    #[must_use]
+    pub fn mean(&self, class: &T) -> Option<&Point> {
+        self.classes.get(class).map(|(_, mean)| mean)
+    }
+
+    /// Predicts the classification of a point using the plug-in rule.
+    #[must_use]
+    pub fn predict(&self, point: &Point) -> T {
+        // run the plug-in rule on this point for all classifications
+        let class_results = self.classes.iter().map(|(class, (_, mean))| {
+            let weights = Weights::new(mean, 0.0);
+            (class, (weights.w.dot(point) - weights.w_0).re)
+        });
+        // find the maximum classification value, pull out class
+        class_results
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .map_or_else(T::default, |(class, _)| class.clone())
+    }
+}
+
+impl<T> Classifier<T> for BayesModel<T>
+where
+    T: Clone + Default + Ord,
+{
+    #[inline]
+    fn train(&mut self, train_data: &[DataPoint<T>]) {
+        *self = Self::fit(train_data);
+    }
+
+    #[inline]
+    fn predict(&self, point: &Point) -> T {
+        Self::predict(self, point)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Creates a labeled two-dimensional data point.
+    fn data_point(x: f64, y: f64, class: &str) -> DataPoint<String> {
+        DataPoint {
+            point: Point(vec![x.into(), y.into()]),
+            class: class.to_owned(),
+            weight: 1.0,
+            id: None,
+        }
+    }
+
+    #[test]
+    fn bayes_gaussian() {
+        let mut train_data = Vec::new();
+        // red is spread out along the x-axis
+        for x in [-4.0, -2.0, 0.0, 2.0, 4.0] {
+            train_data.push(data_point(x, -0.1, "red"));
+            train_data.push(data_point(x, 0.1, "red"));
+        }
+        // blue is tightly clustered
+        for (x, y) in [(2.7, 0.7), (2.7, 1.3), (3.3, 0.7), (3.3, 1.3), (3.0, 1.0)] {
+            train_data.push(data_point(x, y, "blue"));
+        }
+        let test_data = [data_point(4.5, 0.0, "red"), data_point(3.1, 0.9, "blue")];
+
+        // the first test point is nearest to blue's mean
+        let res = bayes_plug_in(&train_data, &test_data);
+        assert_eq!(res[0].class_guess, "blue");
+        assert_eq!(res[1].class_guess, "blue");
+
+        // but it lies along red's distribution
+        let res = bayes_plug_in_gaussian(&train_data, &test_data);
+        assert_eq!(res[0].class_guess, "red");
+        assert_eq!(res[1].class_guess, "blue");
+    }
+
+    #[test]
+    fn bayes_regularized() {
+        // red only varies along the line y = x, so its covariance is singular
+        let train_data = [
+            data_point(0.0, 0.0, "red"),
+            data_point(1.0, 1.0, "red"),
+            data_point(2.0, 2.0, "red"),
+            data_point(4.0, 0.0, "blue"),
+            data_point(5.0, 0.5, "blue"),
+            data_point(4.5, -0.5, "blue"),
+        ];
+        let test_data = [data_point(1.5, 1.2, "red"), data_point(4.2, 0.3, "blue")];
+
+        let res = bayes_plug_in_regularized(&train_data, &test_data, 0.1);
+        assert_eq!(res[0].class_guess, "red");
+        assert_eq!(res[1].class_guess, "blue");
+    }
+
+    #[test]
+    #[should_panic(expected = "singular")]
+    fn bayes_gaussian_singular() {
+        let train_data = [
+            data_point(0.0, 0.0, "red"),
+            data_point(1.0, 1.0, "red"),
+            data_point(2.0, 2.0, "red"),
+        ];
+        let _ = bayes_plug_in_gaussian(&train_data, &train_data);
+    }
+
+    #[test]
+    fn bayes_naive_separable() {
+        let train_data = [
+            data_point(0.0, 0.0, "red"),
+            data_point(1.0, 0.5, "red"),
+            data_point(0.5, 1.0, "red"),
+            data_point(5.0, 5.0, "blue"),
+            data_point(6.0, 5.5, "blue"),
+            data_point(5.5, 6.0, "blue"),
+        ];
+        let test_data = [
+            data_point(0.7, 0.2, "red"),
+            data_point(1.5, 1.5, "red"),
+            data_point(4.5, 5.0, "blue"),
+            data_point(6.5, 6.5, "blue"),
+        ];
+
+        let expected = bayes_plug_in(&train_data, &test_data);
+        let res = bayes_naive(&train_data, &test_data);
+        for (r, e) in res.iter().zip(&expected) {
+            assert_eq!(r.class_guess, e.class_guess);
+            assert_eq!(r.class_guess, r.data.class);
+        }
+    }
+
+    #[test]
+    fn bayes_priors() {
+        let mut train_data = vec![data_point(2.0, 0.0, "blue")];
+        train_data.extend((0..9).map(|_| data_point(0.0, 0.0, "red")));
+        let test_data = [data_point(1.1, 0.0, "blue")];
+
+        // the test point is nearest to blue's mean
+        let res = bayes_plug_in(&train_data, &test_data);
+        assert_eq!(res[0].class_guess, "blue");
+
+        // but red is much more likely
+        let priors = Priors::Given(BTreeMap::from([
+            (String::from("red"), 0.9),
+            (String::from("blue"), 0.1),
+        ]));
+        let res = bayes_plug_in_with_priors(&train_data, &test_data, &priors);
+        assert_eq!(res[0].class_guess, "red");
+
+        let res = bayes_plug_in_with_priors(&train_data, &test_data, &Priors::Estimated);
+        assert_eq!(res[0].class_guess, "red");
+    }
+
+    #[test]
+    fn bayes_posteriors() {
+        let train_data = [
+            data_point(0.0, 0.0, "red"),
+            data_point(0.5, 0.5, "red"),
+            data_point(2.0, 2.0, "blue"),
+            data_point(2.5, 1.5, "blue"),
+            data_point(-2.0, 2.0, "green"),
+        ];
+        let test_data = [
+            data_point(0.2, 0.3, "red"),
+            data_point(1.5, 1.5, "blue"),
+            data_point(-1.0, 1.0, "green"),
+        ];
+
+        let expected = bayes_plug_in(&train_data, &test_data);
+        let res = bayes_plug_in_posteriors(&train_data, &test_data, &Priors::Equal);
+        for ((classification, posteriors), expected) in res.iter().zip(&expected) {
+            assert_eq!(classification.class_guess, expected.class_guess);
+
+            let total: f64 = posteriors.iter().map(|(_, p)| p).sum();
+            assert!((total - 1.0).abs() < 1e-12);
+
+            let (max_class, _) = posteriors
+                .iter()
+                .max_by(|a, b| a.1.total_cmp(&b.1))
+                .unwrap();
+            assert_eq!(*max_class, expected.class_guess);
+        }
+    }
+
+    #[test]
+    fn bayes_posteriors_large_magnitude() {
+        let train_data = [data_point(1e6, -1e6, "red"), data_point(-1e6, 1e6, "blue")];
+        let test_data = [data_point(5e6, -5e6, "red"), data_point(0.0, 0.0, "red")];
+
+        let res = bayes_plug_in_posteriors(&train_data, &test_data, &Priors::Equal);
+        for (_, posteriors) in &res {
+            assert!(posteriors.iter().all(|(_, p)| p.is_finite()));
+            let total: f64 = posteriors.iter().map(|(_, p)| p).sum();
+            assert!((total - 1.0).abs() < 1e-12);
+        }
+        assert_eq!(res[0].0.class_guess, "red");
+    }
+
+    #[test]
+    fn bayes_single_class() {
+        let train_data = [data_point(0.0, 0.0, "red"), data_point(1.0, 1.0, "red")];
+        let test_data = [
+            data_point(0.5, 0.5, "red"),
+            data_point(-10.0, 10.0, "blue"),
+            data_point(100.0, 0.0, "green"),
+        ];
+
+        let res = bayes_plug_in(&train_data, &test_data);
+        assert!(res.iter().all(|r| r.class_guess == "red"));
+    }
+
+    #[test]
+    fn bayes_model_incremental() {
+        let train_data = [
+            data_point(0.0, 0.0, "red"),
+            data_point(1.0, 0.5, "red"),
+            data_point(3.0, 3.0, "blue"),
+            data_point(0.5, 1.0, "red"),
+            data_point(4.0, 3.5, "blue"),
+            data_point(-1.0, 4.0, "green"),
+            data_point(3.5, 4.0, "blue"),
+        ];
+        let test_data = [
+            data_point(0.7, 0.2, "red"),
+            data_point(2.0, 2.0, "blue"),
+            data_point(-0.5, 3.0, "green"),
+            data_point(1.5, 1.5, "red"),
+        ];
+
+        let mut model = BayesModel::new();
+        for d in &train_data {
+            model.update(d);
+        }
+
+        let red_mean = model.mean(&String::from("red")).unwrap();
+        assert!((red_mean.0[0].re - 0.5).abs() < 1e-12);
+        assert!((red_mean.0[1].re - 0.5).abs() < 1e-12);
+
+        let expected = bayes_plug_in(&train_data, &test_data);
+        for (data, e) in test_data.iter().zip(&expected) {
+            assert_eq!(model.predict(&data.point), e.class_guess);
+        }
+    }
+
+    #[test]
+    fn bayes_confidence() {
+        let train_data = [
+            data_point(0.0, 0.0, "red"),
+            data_point(1.0, 0.5, "red"),
+            data_point(0.5, 1.0, "red"),
+            data_point(4.0, 4.0, "blue"),
+            data_point(5.0, 4.5, "blue"),
+            data_point(4.5, 5.0, "blue"),
+        ];
+        let test_data = [data_point(0.5, 0.5, "red"), data_point(2.4, 2.6, "blue")];
+
+        let plug_in = bayes_plug_in(&train_data, &test_data);
+        let gaussian = bayes_plug_in_gaussian(&train_data, &test_data);
+        let naive = bayes_naive(&train_data, &test_data);
+        for c in plug_in.iter().chain(&gaussian).chain(&naive) {
+            let confidence = c.confidence.unwrap();
+            assert!((0.0..=1.0).contains(&confidence));
+        }
+
+        // the point near red's mean is more certain than the point between the means
+        assert!(plug_in[0].confidence > plug_in[1].confidence);
+    }
+
+    #[test]
+    fn bayes_sample_weights() {
+        let mut train_data = vec![
+            data_point(0.0, 0.0, "red"),
+            data_point(1.0, 0.0, "red"),
+            data_point(10.0, 0.0, "red"),
+            data_point(5.0, 0.0, "blue"),
+        ];
+        let test_data = [data_point(7.0, 0.0, "red")];
+
+        // unweighted, the outlier barely moves red's mean
+        let model = BayesModel::fit(&train_data);
+        let red_mean = model.mean(&String::from("red")).unwrap();
+        assert!((red_mean.0[0].re - 11.0 / 3.0).abs() < 1e-12);
+        assert_eq!(
+            bayes_plug_in(&train_data, &test_data)[0].class_guess,
+            "blue"
+        );
+
+        // a heavily-weighted outlier pulls red's mean towards it
+        train_data[2].weight = 8.0;
+        let model = BayesModel::fit(&train_data);
+        let red_mean = model.mean(&String::from("red")).unwrap();
+        assert!((red_mean.0[0].re - 8.1).abs() < 1e-12);
+        assert_eq!(model.predict(&test_data[0].point), "red");
+        assert_eq!(bayes_plug_in(&train_data, &test_data)[0].class_guess, "red");
+
+        // a classification with no weight has the zero point as its mean
+        train_data[3].weight = 0.0;
+        let model = BayesModel::fit(&train_data);
+        let blue_mean = model.mean(&String::from("blue")).unwrap();
+        assert_eq!(*blue_mean, Point(vec![0.0.into(), 0.0.into()]));
+    }
+}
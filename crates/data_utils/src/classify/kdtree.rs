@@ -0,0 +1,268 @@
+//! This module provides a k-d tree to accelerate
+//! nearest neighbor queries on data.
+//!
+//! Author: Benjamin Hall
+
+use super::Neighbor;
+use crate::{DataPoint, Point};
+
+/// Stores a node of a k-d tree.
+struct Node {
+    /// The index of the node's data point
+    index: usize,
+    /// The axis the node splits on
+    axis: usize,
+    /// The index of the node containing data points below the split
+    left: Option<usize>,
+    /// The index of the node containing data points above the split
+    right: Option<usize>,
+}
+
+/// A k-d tree over a set of data points.
+///
+/// Complex components are placed in the tree by their magnitudes,
+/// so distances are the Euclidean distances between the magnitudes
+/// of each point's components. For real data, this is the same as
+/// the Euclidean distance between the points.
+pub struct KdTree<'a, T> {
+    /// The data points in the tree
+    data: &'a [DataPoint<T>],
+    /// The coordinates of each data point
+    coords: Vec<Vec<f64>>,
+    /// The nodes of the tree
+    nodes: Vec<Node>,
+    /// The index of the root node
+    root: Option<usize>,
+}
+
+/// Converts a point to the coordinates used by the tree.
+#[inline]
+fn coordinates(point: &Point, dim: usize) -> Vec<f64> {
+    (0..dim)
+        .map(|i| point.0.get(i).map_or(0.0, |x| x.magnitude()))
+        .collect()
+}
+
+/// Gets the squared distance of the furthest of the `num_neighbors` nearest
+/// data points found so far, or `None` if fewer have been found.
+#[inline]
+fn furthest(best: &[(f64, usize)], num_neighbors: usize) -> Option<f64> {
+    num_neighbors
+        .checked_sub(1)
+        .and_then(|i| best.get(i))
+        .map(|&(d, _)| d)
+}
+
+/// Calculates the squared Euclidean distance between two sets of coordinates.
+#[inline]
+fn squared_distance(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(a, b)| (a - b) * (a - b)).sum()
+}
+
+impl<'a, T> KdTree<'a, T> {
+    /// Builds a k-d tree from the given data.
+    #[must_use]
+    pub fn new(data: &'a [DataPoint<T>]) -> Self {
+        // use the largest dimension of the data, missing components are 0
+        let dim = data.iter().map(|d| d.point.0.len()).max().unwrap_or(0);
+        let coords: Vec<_> = data.iter().map(|d| coordinates(&d.point, dim)).collect();
+
+        let mut tree = Self {
+            data,
+            coords,
+            nodes: Vec::with_capacity(data.len()),
+            root: None,
+        };
+        let mut indices: Vec<_> = (0..data.len()).collect();
+        tree.root = tree.build(&mut indices, 0, dim);
+        tree
+    }
+
+    /// Recursively builds the subtree containing the given data point indices,
+    /// returning the index of the subtree's root node.
+    fn build(&mut self, indices: &mut [usize], depth: usize, dim: usize) -> Option<usize> {
+        if indices.is_empty() {
+            return None;
+        }
+
+        // cycle through the axes, splitting on the median
+        let axis = if dim == 0 { 0 } else { depth % dim };
+        let median = indices.len() / 2;
+        if dim != 0 {
+            let coords = &self.coords;
+            indices.select_nth_unstable_by(median, |&a, &b| {
+                coords[a][axis].total_cmp(&coords[b][axis])
+            });
+        }
+
+        let index = indices[median];
+        let (below, above) = indices.split_at_mut(median);
+        let left = self.build(below, depth + 1, dim);
+        let right = self.build(&mut above[1..], depth + 1, dim);
+
+        self.nodes.push(Node {
+            index,
+            axis,
+            left,
+            right,
+        });
+        Some(self.nodes.len() - 1)
+    }
+
+    /// Finds the `num_neighbors` data points nearest to the given point,
+    /// sorted by distance, then by their order in the data.
+    ///
+    /// If multiple data points are tied at the distance of the furthest
+    /// nearest neighbor, all of them are included, so more than
+    /// `num_neighbors` data points may be returned.
+    #[must_use]
+    pub fn nearest(&self, point: &Point, num_neighbors: usize) -> Vec<Neighbor<'a, T>> {
+        let dim = self.coords.first().map_or(0, Vec::len);
+        let target = coordinates(point, dim);
+
+        // (squared distance, data index) of the nearest data points, sorted by distance
+        let mut best = Vec::with_capacity(num_neighbors + 1);
+        if num_neighbors > 0 {
+            self.search(self.root, &target, num_neighbors, &mut best);
+        }
+
+        best.into_iter()
+            .map(|(dist, index): (f64, usize)| Neighbor {
+                data: &self.data[index],
+                dist: dist.sqrt(),
+            })
+            .collect()
+    }
+
+    /// Recursively searches the subtree for the nearest data points to the target.
+    fn search(
+        &self,
+        node: Option<usize>,
+        target: &[f64],
+        num_neighbors: usize,
+        best: &mut Vec<(f64, usize)>,
+    ) {
+        let Some(node) = node.map(|n| &self.nodes[n]) else {
+            return;
+        };
+
+        // insert this node's data point if it is among the nearest, or tied with the furthest
+        let dist = squared_distance(&self.coords[node.index], target);
+        if furthest(best, num_neighbors).is_none_or(|furthest| dist <= furthest) {
+            let pos = best.partition_point(|&(d, i)| d < dist || (d == dist && i < node.index));
+            best.insert(pos, (dist, node.index));
+
+            // drop any data points that are no longer tied with the furthest
+            if let Some(furthest) = furthest(best, num_neighbors) {
+                let end = best.partition_point(|&(d, _)| d <= furthest);
+                best.truncate(end);
+            }
+        }
+
+        if target.is_empty() {
+            // no axes to split on, search both sides
+            self.search(node.left, target, num_neighbors, best);
+            self.search(node.right, target, num_neighbors, best);
+            return;
+        }
+
+        // search the side of the split containing the target first
+        let diff = target[node.axis] - self.coords[node.index][node.axis];
+        let (near, far) = if diff < 0.0 {
+            (node.left, node.right)
+        } else {
+            (node.right, node.left)
+        };
+        self.search(near, target, num_neighbors, best);
+
+        // only search the other side if it could contain a nearer or tied data point
+        if furthest(best, num_neighbors).is_none_or(|furthest| diff * diff <= furthest) {
+            self.search(far, target, num_neighbors, best);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::classify::{k_nearest_neighbor, k_nearest_neighbor_kdtree, nearest_neighbors};
+
+    #[test]
+    fn kdtree_matches_brute_force() {
+        let mut rng = fastrand::Rng::with_seed(0);
+        let mut random_data = |n: usize| -> Vec<_> {
+            (0..n)
+                .map(|_| DataPoint {
+                    point: Point(
+                        (0..3)
+                            .map(|_| rng.f64().mul_add(2.0, -1.0).into())
+                            .collect(),
+                    ),
+                    class: rng.u8(0..3),
+                    weight: 1.0,
+                    id: None,
+                })
+                .collect()
+        };
+        let train_data = random_data(500);
+        let test_data = random_data(50);
+
+        let tree = KdTree::new(&train_data);
+        let brute_force = nearest_neighbors(&train_data, &test_data, 5);
+        for (data, expected) in test_data.iter().zip(brute_force) {
+            let nearest = tree.nearest(&data.point, 5);
+            assert_eq!(nearest.len(), expected.neighbors.len());
+            for (n, e) in nearest.iter().zip(&expected.neighbors) {
+                assert!((n.dist - e.dist).abs() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn kdtree_ties() {
+        // a grid of integer points, so many distances are exactly tied
+        let train_data: Vec<_> = (0..100)
+            .map(|i| DataPoint {
+                point: Point(vec![f64::from(i % 10).into(), f64::from(i / 10).into()]),
+                class: (i * 7) % 3,
+                weight: 1.0,
+                id: None,
+            })
+            .collect();
+        let test_data: Vec<_> = (0..50)
+            .map(|i| DataPoint {
+                point: Point(vec![f64::from(i % 7).into(), f64::from(i % 9).into()]),
+                class: i % 3,
+                weight: 1.0,
+                id: None,
+            })
+            .collect();
+
+        let tree = KdTree::new(&train_data);
+        for num_neighbors in 1..=6 {
+            // every data point tied with the furthest neighbor is included, in the same order
+            let brute_force = nearest_neighbors(&train_data, &test_data, num_neighbors);
+            for (data, expected) in test_data.iter().zip(brute_force) {
+                let nearest: Vec<_> = tree
+                    .nearest(&data.point, num_neighbors)
+                    .into_iter()
+                    .map(|n| (n.data.point.clone(), n.dist))
+                    .collect();
+                let expected: Vec<_> = expected
+                    .neighbors
+                    .into_iter()
+                    .map(|n| (n.data.point.clone(), n.dist))
+                    .collect();
+                assert_eq!(nearest, expected);
+            }
+
+            // so the classifications match, even with tied votes
+            let kdtree = k_nearest_neighbor_kdtree(&train_data, &test_data, num_neighbors);
+            let brute_force = k_nearest_neighbor(&train_data, &test_data, num_neighbors);
+            for (a, b) in kdtree.iter().zip(&brute_force) {
+                assert_eq!(a.class_guess, b.class_guess);
+                assert_eq!(a.confidence, b.confidence);
+            }
+        }
+    }
+}
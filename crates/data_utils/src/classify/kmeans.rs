@@ -0,0 +1,145 @@
+//! This module provides interfaces to run the
+//! k-means clustering algorithm on data.
+//!
+//! Author: Benjamin Hall
+
+use super::knn::squared_euclidean_distance;
+use crate::Point;
+
+/// Finds the index of the centroid nearest to the given point.
+#[inline]
+fn nearest_centroid(centroids: &[Point], point: &Point) -> usize {
+    centroids
+        .iter()
+        .map(|c| squared_euclidean_distance(c, point))
+        .enumerate()
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map_or(0, |(i, _)| i)
+}
+
+/// Chooses the initial centroids using k-means++, where each new
+/// centroid is chosen with probability proportional to its squared
+/// distance from the nearest centroid already chosen.
+fn initial_centroids(data: &[Point], k: usize, rng: &mut fastrand::Rng) -> Vec<Point> {
+    let mut centroids = vec![data[rng.usize(..data.len())].clone()];
+    let mut distances: Vec<_> = data
+        .iter()
+        .map(|p| squared_euclidean_distance(p, &centroids[0]))
+        .collect();
+
+    while centroids.len() < k {
+        let total: f64 = distances.iter().sum();
+        let index = if total > 0.0 {
+            // walk the cumulative distances to the randomly chosen one
+            let mut target = rng.f64() * total;
+            distances
+                .iter()
+                .position(|&d| {
+                    target -= d;
+                    target < 0.0
+                })
+                .unwrap_or(data.len() - 1)
+        } else {
+            // every point is already a centroid
+            rng.usize(..data.len())
+        };
+
+        let centroid = data[index].clone();
+        for (d, p) in distances.iter_mut().zip(data) {
+            *d = d.min(squared_euclidean_distance(p, &centroid));
+        }
+        centroids.push(centroid);
+    }
+
+    centroids
+}
+
+/// Runs the k-means clustering algorithm on the given data,
+/// returning the centroid of each cluster and the index of the
+/// cluster each point is assigned to.
+///
+/// The initial centroids are chosen using k-means++, and the same
+/// seed always produces the same clusters. The algorithm runs until
+/// the assignments stop changing, for at most `max_iters` iterations.
+///
+/// # Panics
+///
+/// Panics if `k` is 0 or greater than the number of points.
+#[must_use]
+pub fn kmeans(data: &[Point], k: usize, max_iters: usize, seed: u64) -> (Vec<Point>, Vec<usize>) {
+    assert!(
+        (1..=data.len()).contains(&k),
+        "The number of clusters must be between 1 and the number of points"
+    );
+
+    let mut rng = fastrand::Rng::with_seed(seed);
+    let mut centroids = initial_centroids(data, k, &mut rng);
+    let mut assignments: Vec<_> = data
+        .iter()
+        .map(|p| nearest_centroid(&centroids, p))
+        .collect();
+
+    for _ in 0..max_iters {
+        // move each centroid to the mean of its cluster
+        for (i, centroid) in centroids.iter_mut().enumerate() {
+            let cluster: Vec<_> = data
+                .iter()
+                .zip(&assignments)
+                .filter(|&(_, &a)| a == i)
+                .map(|(p, _)| p)
+                .collect();
+            // an empty cluster keeps its centroid
+            if !cluster.is_empty() {
+                let cnt = cluster.len() as f64;
+                *centroid = cluster.into_iter().sum::<Point>().scale(cnt.recip());
+            }
+        }
+
+        // reassign each point to its nearest centroid
+        let new_assignments: Vec<_> = data
+            .iter()
+            .map(|p| nearest_centroid(&centroids, p))
+            .collect();
+        if new_assignments == assignments {
+            break;
+        }
+        assignments = new_assignments;
+    }
+
+    (centroids, assignments)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn two_blobs() {
+        let mut rng = fastrand::Rng::with_seed(0);
+        let mut blob = |x: f64, y: f64| -> Vec<_> {
+            (0..50)
+                .map(|_| {
+                    Point(vec![
+                        (x + rng.f64().mul_add(2.0, -1.0)).into(),
+                        (y + rng.f64().mul_add(2.0, -1.0)).into(),
+                    ])
+                })
+                .collect()
+        };
+        let mut data = blob(0.0, 0.0);
+        data.extend(blob(10.0, 10.0));
+
+        let (mut centroids, assignments) = kmeans(&data, 2, 100, 0);
+        centroids.sort_by(|a, b| a.0[0].re.total_cmp(&b.0[0].re));
+        for (centroid, expected) in centroids.iter().zip([0.0, 10.0]) {
+            for x in &centroid.0 {
+                assert!((x.re - expected).abs() < 0.5);
+            }
+        }
+
+        // each blob is a single cluster
+        assert!(assignments[..50].iter().all(|&a| a == assignments[0]));
+        assert!(assignments[50..].iter().all(|&a| a == assignments[50]));
+        assert_ne!(assignments[0], assignments[50]);
+    }
+}
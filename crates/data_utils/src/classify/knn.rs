@@ -3,12 +3,269 @@
 //!
 //! Author: Benjamin Hall
 
-use super::Classification;
-use crate::{sort::PartialSort, DataPoint};
+use super::{Classification, Classifier, KdTree, Regression};
+use crate::{sort::PartialSort, DataPoint, Point};
 use std::{collections::HashMap, hash::Hash};
 
+/// Offset added to neighbor distances when weighting votes,
+/// so that a neighbor at a distance of zero has a finite weight.
+const WEIGHT_EPSILON: f64 = 1e-9;
+
+/// The proportion of the vote received by each classification.
+pub type VoteProportions<T> = Vec<(T, f64)>;
+
+/// Stores the distance between a training data point and a test data point.
+#[derive(Copy, Clone, Debug)]
+pub struct Neighbor<'a, T> {
+    /// The training data point
+    pub data: &'a DataPoint<T>,
+    /// The distance from the test data point
+    pub dist: f64,
+}
+
+/// Stores the nearest training data points to a test data point.
+#[derive(Clone, Debug)]
+pub struct Neighbors<'a, 'b, T> {
+    /// The test data point
+    pub data: &'a DataPoint<T>,
+    /// The nearest training data points, sorted by distance
+    pub neighbors: Vec<Neighbor<'b, T>>,
+}
+
+/// Calculates the squared Euclidean distance between two points,
+/// where dimensions not present in one of the points are 0.
+///
+/// This avoids allocating a new point for the difference and taking
+/// a square root, and it preserves the ordering of the distances.
+#[inline]
+pub(crate) fn squared_euclidean_distance(a: &Point, b: &Point) -> f64 {
+    // use whichever point has more dimensions
+    let (long, short) = if a.0.len() >= b.0.len() {
+        (a, b)
+    } else {
+        (b, a)
+    };
+    long.0
+        .iter()
+        .enumerate()
+        .map(|(i, &x)| {
+            let diff = short.0.get(i).map_or(x, |&y| x - y);
+            diff.re.mul_add(diff.re, diff.im * diff.im)
+        })
+        .sum()
+}
+
+/// Finds the `num_neighbors` training data points nearest to the given point
+/// using the given distance function, sorted by distance.
+///
+/// If multiple training data points are tied at the distance of the furthest
+/// nearest neighbor, all of them are included, so more than `num_neighbors`
+/// training data points may be returned. This makes the result independent
+/// of the order of the training data. Neighbors at the same distance are
+/// kept in the order of the training data.
+fn find_nearest_neighbors<'a, T, F>(
+    train_data: impl IntoIterator<Item = &'a DataPoint<T>>,
+    point: &Point,
+    num_neighbors: usize,
+    distance: &F,
+) -> Vec<Neighbor<'a, T>>
+where
+    F: Fn(&Point, &Point) -> f64,
+{
+    // calculate distance between training data points and the test data point
+    let mut distances: Vec<_> = train_data
+        .into_iter()
+        .map(|d| Neighbor {
+            data: d,
+            dist: distance(&d.point, point),
+        })
+        .collect();
+    // perform a partial sort of the training data distances, up to num_neighbors
+    let num_nearest = num_neighbors.min(distances.len());
+    distances.partial_sort_by(num_nearest, |d1, d2| d1.dist.total_cmp(&d2.dist));
+
+    // pull out the nearest neighbors, along with any ties at the furthest distance
+    let mut end = num_nearest;
+    if let Some(boundary) = num_nearest.checked_sub(1).map(|i| distances[i].dist) {
+        for i in num_nearest..distances.len() {
+            if distances[i].dist == boundary {
+                distances.swap(i, end);
+                end += 1;
+            }
+        }
+    }
+    distances.truncate(end);
+    distances
+}
+
+/// Finds the `num_neighbors` training data points nearest to the given point
+/// by Euclidean distance, sorted by distance, including any ties.
+fn find_nearest_neighbors_euclidean<'a, T>(
+    train_data: impl IntoIterator<Item = &'a DataPoint<T>>,
+    point: &Point,
+    num_neighbors: usize,
+) -> Vec<Neighbor<'a, T>> {
+    // sort by squared distance, then only take the square root of the nearest neighbors
+    let mut nearest = find_nearest_neighbors(
+        train_data,
+        point,
+        num_neighbors,
+        &squared_euclidean_distance,
+    );
+    for d in &mut nearest {
+        d.dist = d.dist.sqrt();
+    }
+    nearest
+}
+
+/// Tallies the weighted votes for each classification, returning the
+/// proportion of the total vote received by each classification,
+/// sorted from the most to the fewest votes.
+///
+/// Classifications with exactly the same votes are kept in the order they
+/// first appear in the votes. The votes are given nearest neighbor first,
+/// so a tied vote goes to the classification of the nearest neighbor.
+fn vote_proportions<'a, T>(votes: impl IntoIterator<Item = (&'a T, f64)>) -> Vec<(&'a T, f64)>
+where
+    T: 'a + Eq + Hash,
+{
+    // count how many votes are present for each classification,
+    // in the order the classifications first appear
+    let mut tally: Vec<(&T, f64)> = Vec::new();
+    let mut index: HashMap<&T, usize> = HashMap::new();
+    for (class, weight) in votes {
+        let i = *index.entry(class).or_insert_with(|| {
+            tally.push((class, 0.0));
+            tally.len() - 1
+        });
+        tally[i].1 += weight;
+    }
+
+    // normalize the votes by the total vote
+    let total: f64 = tally.iter().map(|&(_, votes)| votes).sum();
+    for (_, votes) in &mut tally {
+        *votes /= total;
+    }
+    // stable sort, so ties stay in order of first appearance
+    tally.sort_by(|a, b| b.1.total_cmp(&a.1));
+    tally
+}
+
+/// Returns the classification with the most votes and its proportion of the
+/// vote, given the vote proportions sorted from the most to the fewest votes.
+fn top_vote<T>(proportions: &[(&T, f64)]) -> (T, Option<f64>)
+where
+    T: Clone + Default,
+{
+    // majority vote: first proportion has the most votes, pull out classification
+    proportions.first().map_or_else(
+        || (T::default(), None),
+        |&(class, proportion)| (class.clone(), Some(proportion)),
+    )
+}
+
+/// Tallies the weighted votes for each classification, returning the
+/// classification with the most votes and its proportion of the vote.
+#[inline]
+fn majority_vote<'a, T>(votes: impl IntoIterator<Item = (&'a T, f64)>) -> (T, Option<f64>)
+where
+    T: 'a + Clone + Default + Eq + Hash,
+{
+    top_vote(&vote_proportions(votes))
+}
+
+/// Classifies a test data point by a majority vote of the given weighted votes.
+#[inline]
+fn vote_classification<'a, 'b, T>(
+    data: &'a DataPoint<T>,
+    votes: impl IntoIterator<Item = (&'b T, f64)>,
+) -> Classification<'a, T>
+where
+    T: 'b + Clone + Default + Eq + Hash,
+{
+    let (class_guess, confidence) = majority_vote(votes);
+    // wrap in a Classification
+    Classification {
+        data,
+        class_guess,
+        confidence,
+    }
+}
+
+/// Runs the given function on each test data point, after checking
+/// that there is enough training data for the number of neighbors.
+fn for_each_test_point<'a, T, U, R>(
+    train_data: &[DataPoint<U>],
+    test_data: &'a [DataPoint<T>],
+    num_neighbors: usize,
+    f: impl FnMut(&'a DataPoint<T>) -> R,
+) -> Vec<R> {
+    assert!(
+        train_data.len() >= num_neighbors,
+        "Not enough training data for {num_neighbors} neighbors"
+    );
+
+    test_data.iter().map(f).collect()
+}
+
+/// A k-nearest neighbor classifier.
+///
+/// Training only stores a copy of the training data,
+/// which is searched for the neighbors of each point.
+#[derive(Clone, Debug, Default)]
+pub struct KNearestNeighbor<T> {
+    /// The number of neighbors that vote on a classification
+    pub num_neighbors: usize,
+    /// The training data
+    train_data: Vec<DataPoint<T>>,
+}
+
+impl<T> KNearestNeighbor<T> {
+    /// Creates an untrained classifier with the specified number of neighbors.
+    #[inline]
+    #[must_use]
+    pub const fn new(num_neighbors: usize) -> Self {
+        Self {
+            num_neighbors,
+            train_data: Vec::new(),
+        }
+    }
+}
+
+impl<T> Classifier<T> for KNearestNeighbor<T>
+where
+    T: Clone + Default + Eq + Hash,
+{
+    fn train(&mut self, train_data: &[DataPoint<T>]) {
+        assert!(
+            train_data.len() >= self.num_neighbors,
+            "Not enough training data for {} neighbors",
+            self.num_neighbors
+        );
+        self.train_data = train_data.to_vec();
+    }
+
+    fn predict(&self, point: &Point) -> T {
+        let nearest = find_nearest_neighbors(
+            &self.train_data,
+            point,
+            self.num_neighbors,
+            &squared_euclidean_distance,
+        );
+        // each neighbor gets one vote
+        majority_vote(nearest.iter().map(|d| (&d.data.class, 1.0))).0
+    }
+}
+
 /// Runs the k-nearest neighbor algorithm with the given training data
 /// on the given test data for the specified number of neighbors.
+///
+/// If multiple training data points are tied at the distance of the
+/// `num_neighbors`-th nearest neighbor, all of them get a vote, so the
+/// result does not depend on the order of the training data. If the vote
+/// itself is tied, the classification of the nearest neighbor among the
+/// tied classifications is chosen.
+#[inline]
 #[must_use]
 pub fn k_nearest_neighbor<'a, T>(
     train_data: &[DataPoint<T>],
@@ -18,53 +275,573 @@ pub fn k_nearest_neighbor<'a, T>(
 where
     T: Clone + Default + Eq + Hash,
 {
+    // squared distances preserve the ordering of the neighbors
+    k_nearest_neighbor_by(
+        train_data,
+        test_data,
+        num_neighbors,
+        squared_euclidean_distance,
+    )
+}
+
+/// Runs the k-nearest neighbor algorithm with the given training data
+/// on the given test data for the specified number of neighbors, using
+/// the given function to calculate the distance between two points.
+#[must_use]
+pub fn k_nearest_neighbor_by<'a, T, F>(
+    train_data: &[DataPoint<T>],
+    test_data: &'a [DataPoint<T>],
+    num_neighbors: usize,
+    distance: F,
+) -> Vec<Classification<'a, T>>
+where
+    T: Clone + Default + Eq + Hash,
+    F: Fn(&Point, &Point) -> f64,
+{
+    // run k-nearest neighbor on all test data and collect the results
+    for_each_test_point(train_data, test_data, num_neighbors, |data| {
+        let nearest = find_nearest_neighbors(train_data, &data.point, num_neighbors, &distance);
+        // each neighbor gets one vote
+        vote_classification(data, nearest.iter().map(|d| (&d.data.class, 1.0)))
+    })
+}
+
+/// Runs the k-nearest neighbor algorithm with the given training data
+/// on the given test data for the specified number of neighbors,
+/// using a k-d tree to find the nearest neighbors.
+///
+/// For low-dimensional data with many training data points, this is
+/// much faster than checking every training data point. Complex
+/// components are compared by their magnitudes.
+///
+/// As with [`k_nearest_neighbor`], all training data points tied at the
+/// distance of the `num_neighbors`-th nearest neighbor get a vote, and
+/// a tied vote goes to the classification of the nearest neighbor.
+#[must_use]
+pub fn k_nearest_neighbor_kdtree<'a, T>(
+    train_data: &[DataPoint<T>],
+    test_data: &'a [DataPoint<T>],
+    num_neighbors: usize,
+) -> Vec<Classification<'a, T>>
+where
+    T: Clone + Default + Eq + Hash,
+{
+    // build the k-d tree once for all test data
+    let tree = KdTree::new(train_data);
+
+    // run k-nearest neighbor on all test data and collect the results
+    for_each_test_point(train_data, test_data, num_neighbors, |data| {
+        let nearest = tree.nearest(&data.point, num_neighbors);
+        // each neighbor gets one vote
+        vote_classification(data, nearest.iter().map(|d| (&d.data.class, 1.0)))
+    })
+}
+
+/// Runs the distance-weighted k-nearest neighbor algorithm with the given
+/// training data on the given test data for the specified number of neighbors.
+///
+/// Each neighbor's vote is weighted by the inverse of its distance from the
+/// test data point, so closer neighbors have more influence on the result.
+#[must_use]
+pub fn k_nearest_neighbor_weighted<'a, T>(
+    train_data: &[DataPoint<T>],
+    test_data: &'a [DataPoint<T>],
+    num_neighbors: usize,
+) -> Vec<Classification<'a, T>>
+where
+    T: Clone + Default + Eq + Hash,
+{
+    // run weighted k-nearest neighbor on all test data and collect the results
+    for_each_test_point(train_data, test_data, num_neighbors, |data| {
+        let nearest = find_nearest_neighbors_euclidean(train_data, &data.point, num_neighbors);
+        // each neighbor's vote is weighted by 1 / distance
+        vote_classification(
+            data,
+            nearest
+                .iter()
+                .map(|d| (&d.data.class, (d.dist + WEIGHT_EPSILON).recip())),
+        )
+    })
+}
+
+/// Runs the k-nearest neighbor algorithm with the given training data
+/// on the given test data for the specified number of neighbors.
+///
+/// Alongside each classification, this returns the proportion of the
+/// nearest neighbors that voted for each classification, sorted from
+/// the most to the fewest votes. The proportion of the first entry can
+/// be used as the confidence of the classification.
+#[must_use]
+pub fn k_nearest_neighbor_proba<'a, T>(
+    train_data: &[DataPoint<T>],
+    test_data: &'a [DataPoint<T>],
+    num_neighbors: usize,
+) -> Vec<(Classification<'a, T>, VoteProportions<T>)>
+where
+    T: Clone + Default + Eq + Hash,
+{
+    // run k-nearest neighbor on all test data and collect the results
+    for_each_test_point(train_data, test_data, num_neighbors, |data| {
+        let nearest = find_nearest_neighbors_euclidean(train_data, &data.point, num_neighbors);
+        // each neighbor gets one vote
+        let proportions = vote_proportions(nearest.iter().map(|d| (&d.data.class, 1.0)));
+        let (class_guess, confidence) = top_vote(&proportions);
+        // wrap in a Classification
+        let classification = Classification {
+            data,
+            class_guess,
+            confidence,
+        };
+        let proportions = proportions
+            .into_iter()
+            .map(|(class, proportion)| (class.clone(), proportion))
+            .collect();
+        (classification, proportions)
+    })
+}
+
+/// Finds the nearest neighbors in the given training data for each
+/// data point in the given test data, for the specified number of neighbors.
+///
+/// This returns the training data points that would vote on each test data
+/// point's classification, along with their distances, sorted by distance.
+/// This includes any training data points tied at the distance of the
+/// `num_neighbors`-th nearest neighbor.
+#[must_use]
+pub fn nearest_neighbors<'a, 'b, T>(
+    train_data: &'b [DataPoint<T>],
+    test_data: &'a [DataPoint<T>],
+    num_neighbors: usize,
+) -> Vec<Neighbors<'a, 'b, T>> {
+    // find the nearest neighbors of all test data and collect the results
+    for_each_test_point(train_data, test_data, num_neighbors, |data| Neighbors {
+        data,
+        neighbors: find_nearest_neighbors_euclidean(train_data, &data.point, num_neighbors),
+    })
+}
+
+/// Runs the k-nearest neighbor algorithm with the given training data
+/// on the given test data for the specified number of neighbors.
+///
+/// If `exclude_exact_match` is set, training data points that are identical
+/// to a test data point are not counted as its neighbors. This allows the
+/// training data to be classified against itself, e.g. for leave-one-out
+/// evaluation, without each data point voting for itself.
+#[must_use]
+pub fn k_nearest_neighbor_excluding<'a, T>(
+    train_data: &[DataPoint<T>],
+    test_data: &'a [DataPoint<T>],
+    num_neighbors: usize,
+    exclude_exact_match: bool,
+) -> Vec<Classification<'a, T>>
+where
+    T: Clone + Default + Eq + Hash,
+{
+    // run k-nearest neighbor on all test data and collect the results
+    for_each_test_point(train_data, test_data, num_neighbors, |data| {
+        // skip training data points at the same location as the test data point
+        let candidates = train_data
+            .iter()
+            .filter(|d| !exclude_exact_match || d.point != data.point);
+        let nearest = find_nearest_neighbors_euclidean(candidates, &data.point, num_neighbors);
+        // each neighbor gets one vote
+        vote_classification(data, nearest.iter().map(|d| (&d.data.class, 1.0)))
+    })
+}
+
+/// Runs the k-nearest neighbor regression algorithm with the given training
+/// data on the given test data for the specified number of neighbors.
+///
+/// The prediction for each test data point is the mean of the values of its
+/// nearest neighbors. If `weighted` is set, each neighbor's value is weighted
+/// by the inverse of its distance from the test data point.
+///
+/// # Panics
+///
+/// Panics if `num_neighbors` is 0, since there would be no values to average,
+/// or if there is not enough training data for `num_neighbors` neighbors.
+#[must_use]
+pub fn k_nearest_neighbor_regression<'a, T>(
+    train_data: &[DataPoint<f64>],
+    test_data: &'a [DataPoint<T>],
+    num_neighbors: usize,
+    weighted: bool,
+) -> Vec<Regression<'a, T>> {
     assert!(
-        train_data.len() >= num_neighbors,
-        "Not enough training data for {num_neighbors} neighbors"
+        num_neighbors > 0,
+        "Cannot average the values of 0 neighbors"
     );
 
-    // run k-nearest neighbor on all test data and collect the results
+    // run k-nearest neighbor regression on all test data and collect the results
+    for_each_test_point(train_data, test_data, num_neighbors, |data| {
+        let nearest = find_nearest_neighbors_euclidean(train_data, &data.point, num_neighbors);
+
+        // weight each neighbor's value, either equally or by 1 / distance
+        let (total, total_weight) = nearest
+            .iter()
+            .map(|d| {
+                let weight = if weighted {
+                    (d.dist + WEIGHT_EPSILON).recip()
+                } else {
+                    1.0
+                };
+                (weight * d.data.class, weight)
+            })
+            .fold((0.0, 0.0), |a, b| (a.0 + b.0, a.1 + b.1));
+        let prediction = total / total_weight;
+        // wrap in a Regression
+        Regression { data, prediction }
+    })
+}
+
+/// Runs a radius-based nearest neighbor algorithm with the given training
+/// data on the given test data for the specified radius.
+///
+/// Each test data point is classified by a majority vote of all training
+/// data points within `radius` of it. If there are no training data points
+/// within the radius, the test data point is given the classification of
+/// the nearest training data point.
+#[must_use]
+pub fn radius_neighbors<'a, T>(
+    train_data: &[DataPoint<T>],
+    test_data: &'a [DataPoint<T>],
+    radius: f64,
+) -> Vec<Classification<'a, T>>
+where
+    T: Clone + Default + Eq + Hash,
+{
+    // run radius neighbors on all test data and collect the results
     test_data
         .iter()
         .map(|data| {
-            /// Stores the distance between a training data point and the current test data point.
-            struct Dist<'a, T> {
-                /// The training data point
-                data: &'a DataPoint<T>,
-                /// The distance from the test data point
-                dist: f64,
-            }
-
             // calculate distance between training data points and the test data point
-            let mut distances: Vec<_> = train_data
+            let distances: Vec<_> = train_data
                 .iter()
-                .map(|d| Dist {
+                .map(|d| Neighbor {
                     data: d,
-                    dist: (&d.point - &data.point).magnitude(),
+                    dist: squared_euclidean_distance(&d.point, &data.point),
                 })
                 .collect();
-            // perform a partial sort of the training data distances, up to num_neighbors
-            distances.partial_sort_by(num_neighbors, |d1, d2| d1.dist.total_cmp(&d2.dist));
-
-            // pull out the nearest neighbors
-            let nearest = &distances[0..num_neighbors];
-            // count how many votes are present for each classification
-            let mut votes: HashMap<&T, usize> = HashMap::with_capacity(num_neighbors);
-            for d in nearest {
-                if let Some(v) = votes.get_mut(&d.data.class) {
-                    *v += 1;
-                } else {
-                    votes.insert(&d.data.class, 1);
-                }
-            }
 
-            // majority vote: max by value, pull out classification
-            let class_guess = votes
+            // pull out the neighbors within the radius, comparing squared distances,
+            // nearest first so a tied vote goes to the nearest neighbor
+            let radius_squared = radius * radius;
+            let mut in_range: Vec<_> = distances
                 .iter()
-                .max_by(|a, b| a.1.cmp(b.1))
-                .map_or_else(T::default, |(&class, _)| class.clone());
+                .filter(|d| d.dist <= radius_squared)
+                .collect();
+            in_range.sort_by(|d1, d2| d1.dist.total_cmp(&d2.dist));
+            let (class_guess, confidence) = if !in_range.is_empty() {
+                // each neighbor in range gets one vote
+                majority_vote(in_range.iter().map(|d| (&d.data.class, 1.0)))
+            } else {
+                // fall back to the nearest neighbor, the only vote
+                distances
+                    .iter()
+                    .min_by(|d1, d2| d1.dist.total_cmp(&d2.dist))
+                    .map_or_else(
+                        || (T::default(), None),
+                        |d| (d.data.class.clone(), Some(1.0)),
+                    )
+            };
             // wrap in a Classification
-            Classification { data, class_guess }
+            Classification {
+                data,
+                class_guess,
+                confidence,
+            }
         })
         .collect()
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Complex;
+
+    /// Calculates the Manhattan distance between two points.
+    fn manhattan_distance(a: &Point, b: &Point) -> f64 {
+        (a - b).0.iter().map(|x| x.re.hypot(x.im)).sum()
+    }
+
+    /// Creates a labeled one-dimensional data point.
+    fn data_point(x: f64, class: &str) -> DataPoint<String> {
+        DataPoint {
+            point: Point(vec![x.into()]),
+            class: class.to_owned(),
+            weight: 1.0,
+            id: None,
+        }
+    }
+
+    #[test]
+    fn knn_by_distance() {
+        let train_data = [
+            DataPoint {
+                point: Point(vec![1.5.into(), 0.0.into()]),
+                class: String::from("red"),
+                weight: 1.0,
+                id: None,
+            },
+            DataPoint {
+                point: Point(vec![1.0.into(), 1.0.into()]),
+                class: String::from("blue"),
+                weight: 1.0,
+                id: None,
+            },
+        ];
+        let test_data = [DataPoint {
+            point: Point(vec![0.0.into(), 0.0.into()]),
+            class: String::from("red"),
+            weight: 1.0,
+            id: None,
+        }];
+
+        // the diagonal point is closer by Euclidean distance
+        let res = k_nearest_neighbor(&train_data, &test_data, 1);
+        assert_eq!(res[0].class_guess, "blue");
+
+        // the axis-aligned point is closer by Manhattan distance
+        let res = k_nearest_neighbor_by(&train_data, &test_data, 1, manhattan_distance);
+        assert_eq!(res[0].class_guess, "red");
+    }
+
+    #[test]
+    fn knn_weighted() {
+        let train_data = [
+            data_point(0.1, "red"),
+            data_point(1.0, "blue"),
+            data_point(1.1, "blue"),
+            data_point(1.2, "blue"),
+        ];
+        let test_data = [data_point(0.0, "red")];
+
+        // the distant neighbors win a plain majority vote
+        let res = k_nearest_neighbor(&train_data, &test_data, 4);
+        assert_eq!(res[0].class_guess, "blue");
+
+        // the very close neighbor outvotes the distant neighbors
+        let res = k_nearest_neighbor_weighted(&train_data, &test_data, 4);
+        assert_eq!(res[0].class_guess, "red");
+    }
+
+    #[test]
+    fn knn_proba() {
+        let train_data = [
+            data_point(0.1, "red"),
+            data_point(0.2, "red"),
+            data_point(0.3, "red"),
+            data_point(0.4, "blue"),
+            data_point(5.0, "blue"),
+        ];
+        let test_data = [data_point(0.0, "red")];
+
+        let res = k_nearest_neighbor_proba(&train_data, &test_data, 4);
+        let (classification, proportions) = &res[0];
+        assert_eq!(classification.class_guess, "red");
+        assert_eq!(
+            *proportions,
+            [(String::from("red"), 0.75), (String::from("blue"), 0.25)]
+        );
+    }
+
+    #[test]
+    fn knn_nearest_neighbors() {
+        let train_data = [
+            data_point(4.0, "blue"),
+            data_point(-0.5, "red"),
+            data_point(3.0, "blue"),
+            data_point(0.25, "red"),
+            data_point(-2.0, "blue"),
+        ];
+        let test_data = [data_point(0.0, "red")];
+
+        let res = nearest_neighbors(&train_data, &test_data, 3);
+        let neighbors: Vec<_> = res[0].neighbors.iter().map(|n| (n.data, n.dist)).collect();
+        assert_eq!(
+            neighbors,
+            [
+                (&train_data[3], 0.25),
+                (&train_data[1], 0.5),
+                (&train_data[4], 2.0)
+            ]
+        );
+    }
+
+    #[test]
+    fn knn_radius_neighbors() {
+        let train_data = [
+            data_point(0.5, "red"),
+            data_point(-1.0, "blue"),
+            data_point(1.0, "blue"),
+            data_point(1.5, "blue"),
+            data_point(10.0, "red"),
+        ];
+        let test_data = [data_point(0.0, "blue"), data_point(7.0, "red")];
+
+        let res = radius_neighbors(&train_data, &test_data, 1.25);
+        // three neighbors in range, two of which are blue
+        assert_eq!(res[0].class_guess, "blue");
+        // no neighbors in range, falls back to the nearest neighbor
+        assert_eq!(res[1].class_guess, "red");
+    }
+
+    #[test]
+    fn knn_regression() {
+        // y = 2x + 1
+        let train_data: Vec<_> = (0..10)
+            .map(|x| DataPoint {
+                point: Point(vec![f64::from(x).into()]),
+                class: 2.0 * f64::from(x) + 1.0,
+                weight: 1.0,
+                id: None,
+            })
+            .collect();
+        let test_data = [
+            DataPoint {
+                point: Point(vec![4.5.into()]),
+                class: 10.0,
+                weight: 1.0,
+                id: None,
+            },
+            DataPoint {
+                point: Point(vec![4.2.into()]),
+                class: 9.4,
+                weight: 1.0,
+                id: None,
+            },
+        ];
+
+        let res = k_nearest_neighbor_regression(&train_data, &test_data, 2, false);
+        assert!((res[0].prediction - 10.0).abs() < 1e-6);
+        assert!((res[1].prediction - 10.0).abs() < 1e-6);
+
+        let res = k_nearest_neighbor_regression(&train_data, &test_data, 2, true);
+        assert!((res[0].prediction - 10.0).abs() < 1e-6);
+        assert!((res[1].prediction - 9.4).abs() < 1e-6);
+    }
+
+    #[test]
+    #[should_panic(expected = "Cannot average the values of 0 neighbors")]
+    fn knn_regression_no_neighbors() {
+        let train_data = [DataPoint {
+            point: Point(vec![0.0.into()]),
+            class: 1.0,
+            weight: 1.0,
+            id: None,
+        }];
+        let _ = k_nearest_neighbor_regression(&train_data, &train_data, 0, false);
+    }
+
+    #[test]
+    fn knn_squared_distance_matches_magnitude() {
+        let mut rng = fastrand::Rng::with_seed(0);
+        let mut random_data = |n: usize| -> Vec<_> {
+            (0..n)
+                .map(|_| DataPoint {
+                    point: Point(
+                        (0..3)
+                            .map(|_| Complex {
+                                re: rng.f64().mul_add(2.0, -1.0),
+                                im: rng.f64().mul_add(2.0, -1.0),
+                            })
+                            .collect(),
+                    ),
+                    class: rng.bool(),
+                    weight: 1.0,
+                    id: None,
+                })
+                .collect()
+        };
+        let train_data = random_data(1000);
+        let test_data = random_data(100);
+
+        // an odd number of neighbors with two classes cannot tie
+        let res = k_nearest_neighbor(&train_data, &test_data, 5);
+        let expected =
+            k_nearest_neighbor_by(&train_data, &test_data, 5, |a, b| (a - b).magnitude());
+        for (r, e) in res.iter().zip(&expected) {
+            assert_eq!(r.class_guess, e.class_guess);
+        }
+    }
+
+    #[test]
+    fn knn_boundary_ties() {
+        let train_data = [
+            data_point(1.0, "red"),
+            data_point(2.0, "blue"),
+            data_point(2.0, "green"),
+            data_point(-2.0, "blue"),
+            data_point(3.0, "green"),
+        ];
+        let test_data = [data_point(0.0, "blue")];
+
+        // all three points at a distance of 2 are tied for the second neighbor
+        let res = nearest_neighbors(&train_data, &test_data, 2);
+        let dists: Vec<_> = res[0].neighbors.iter().map(|n| n.dist).collect();
+        assert_eq!(dists, [1.0, 2.0, 2.0, 2.0]);
+
+        // the tied points all vote, so blue wins regardless of order
+        let res = k_nearest_neighbor(&train_data, &test_data, 2);
+        assert_eq!(res[0].class_guess, "blue");
+    }
+
+    #[test]
+    fn knn_tied_vote() {
+        let test_data = [data_point(0.0, "red")];
+        for _ in 0..10 {
+            // one vote each, so the nearest neighbor's classification wins
+            let train_data = [data_point(1.0, "red"), data_point(-2.0, "blue")];
+            let res = k_nearest_neighbor(&train_data, &test_data, 2);
+            assert_eq!(res[0].class_guess, "red");
+            assert_eq!(res[0].confidence, Some(0.5));
+
+            let train_data = [data_point(-2.0, "red"), data_point(1.0, "blue")];
+            let res = k_nearest_neighbor(&train_data, &test_data, 2);
+            assert_eq!(res[0].class_guess, "blue");
+
+            // the same holds within a radius
+            let res = radius_neighbors(&train_data, &test_data, 3.0);
+            assert_eq!(res[0].class_guess, "blue");
+        }
+    }
+
+    #[test]
+    fn knn_exclude_exact_match() {
+        let train_data = [
+            data_point(0.0, "red"),
+            data_point(0.4, "blue"),
+            data_point(0.5, "blue"),
+            data_point(3.0, "red"),
+        ];
+
+        // each point is its own nearest neighbor
+        let res = k_nearest_neighbor_excluding(&train_data, &train_data, 1, false);
+        assert_eq!(res[0].class_guess, "red");
+
+        // excluding the point itself, its nearest neighbor is blue
+        let res = k_nearest_neighbor_excluding(&train_data, &train_data, 1, true);
+        assert_eq!(res[0].class_guess, "blue");
+    }
+
+    #[test]
+    fn knn_confidence() {
+        let train_data = [
+            data_point(0.0, "red"),
+            data_point(1.0, "red"),
+            data_point(2.0, "blue"),
+            data_point(10.0, "blue"),
+        ];
+        let test_data = [data_point(0.5, "red"), data_point(9.0, "blue")];
+
+        let res = k_nearest_neighbor(&train_data, &test_data, 3);
+        assert_eq!(res[0].confidence, Some(2.0 / 3.0));
+        for c in k_nearest_neighbor_weighted(&train_data, &test_data, 3)
+            .iter()
+            .chain(&res)
+        {
+            let confidence = c.confidence.unwrap();
+            assert!((0.0..=1.0).contains(&confidence));
+        }
+    }
+}
@@ -0,0 +1,391 @@
+//! This module provides interfaces to evaluate
+//! the results of classification algorithms.
+//!
+//! Author: Benjamin Hall
+
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::{self, Display},
+    hash::Hash,
+};
+
+use super::{accuracy, Classification};
+use crate::DataPoint;
+
+/// Stores the evaluation metrics of a single classification.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct ClassMetrics {
+    /// The fraction of guesses of the classification that were correct
+    pub precision: f64,
+    /// The fraction of data of the classification that was guessed correctly
+    pub recall: f64,
+    /// The harmonic mean of the precision and recall
+    pub f1: f64,
+    /// The number of data points with the classification
+    pub support: usize,
+}
+
+/// Divides two counts, returning 0 if the denominator is 0.
+#[inline]
+fn ratio(numerator: usize, denominator: usize) -> f64 {
+    if denominator == 0 {
+        0.0
+    } else {
+        numerator as f64 / denominator as f64
+    }
+}
+
+/// Counts the classification results by their actual
+/// classification and the classification guess.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ConfusionMatrix<T>
+where
+    T: Eq + Hash,
+{
+    /// The classifications, in the order they first appear in the results
+    classes: Vec<T>,
+    /// The number of results for each (actual, guess) pair of classifications
+    counts: HashMap<(T, T), usize>,
+}
+
+impl<T> ConfusionMatrix<T>
+where
+    T: Clone + Eq + Hash,
+{
+    /// Builds a confusion matrix from the results of a classification algorithm.
+    #[must_use]
+    pub fn new(results: &[Classification<T>]) -> Self {
+        let mut classes = Vec::new();
+        let mut counts = HashMap::new();
+        for c in results {
+            for class in [&c.data.class, &c.class_guess] {
+                if !classes.contains(class) {
+                    classes.push(class.clone());
+                }
+            }
+            *counts
+                .entry((c.data.class.clone(), c.class_guess.clone()))
+                .or_insert(0) += 1;
+        }
+
+        Self { classes, counts }
+    }
+
+    /// Gets the classifications in the confusion matrix, in
+    /// the order they first appear in the results.
+    #[inline]
+    #[must_use]
+    pub fn classes(&self) -> &[T] {
+        &self.classes
+    }
+
+    /// Gets the number of results with the given actual
+    /// classification that were classified as the given guess.
+    #[inline]
+    #[must_use]
+    pub fn get(&self, actual: &T, guess: &T) -> usize {
+        self.counts
+            .get(&(actual.clone(), guess.clone()))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Gets the total number of results in the confusion matrix.
+    #[inline]
+    #[must_use]
+    pub fn total(&self) -> usize {
+        self.counts.values().sum()
+    }
+
+    /// Calculates the precision, recall, and F1 score of a classification.
+    ///
+    /// Metrics that would divide by zero are 0.
+    #[must_use]
+    pub fn class_metrics(&self, class: &T) -> ClassMetrics {
+        let mut true_pos = 0;
+        let mut false_pos = 0;
+        let mut false_neg = 0;
+        for ((actual, guess), &cnt) in &self.counts {
+            match (actual == class, guess == class) {
+                (true, true) => true_pos += cnt,
+                (false, true) => false_pos += cnt,
+                (true, false) => false_neg += cnt,
+                (false, false) => {}
+            }
+        }
+
+        let precision = ratio(true_pos, true_pos + false_pos);
+        let recall = ratio(true_pos, true_pos + false_neg);
+        // F1 = 2TP / (2TP + FP + FN), the harmonic mean of precision and recall
+        let f1 = ratio(2 * true_pos, 2 * true_pos + false_pos + false_neg);
+
+        ClassMetrics {
+            precision,
+            recall,
+            f1,
+            support: true_pos + false_neg,
+        }
+    }
+}
+
+/// Calculates the precision, recall, and F1 score of each
+/// classification in the results of a classification algorithm.
+#[must_use]
+pub fn class_metrics<T>(results: &[Classification<T>]) -> HashMap<T, ClassMetrics>
+where
+    T: Clone + Eq + Hash,
+{
+    let matrix = ConfusionMatrix::new(results);
+    matrix
+        .classes()
+        .iter()
+        .map(|class| (class.clone(), matrix.class_metrics(class)))
+        .collect()
+}
+
+impl<T> Display for ConfusionMatrix<T>
+where
+    T: Clone + Display + Eq + Hash,
+{
+    /// Formats the confusion matrix as a table with a row for
+    /// each actual classification and a column for each guess.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let labels: Vec<_> = self.classes.iter().map(ToString::to_string).collect();
+        // every column is as wide as the widest label or count
+        let width = labels
+            .iter()
+            .map(String::len)
+            .chain(self.counts.values().map(|cnt| cnt.to_string().len()))
+            .max()
+            .unwrap_or(0);
+
+        // header row of guesses
+        write!(f, "{:width$}", "")?;
+        for label in &labels {
+            write!(f, " {label:>width$}")?;
+        }
+
+        // one row per actual classification
+        for (actual, label) in self.classes.iter().zip(&labels) {
+            write!(f, "\n{label:>width$}")?;
+            for guess in &self.classes {
+                write!(f, " {:>width$}", self.get(actual, guess))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Calculates the mean recall of the classifications in the results
+/// of a classification algorithm.
+///
+/// Unlike accuracy, every classification counts equally, so a
+/// classification with little data is not outweighed by the others.
+/// Classifications that were guessed but do not appear in the data are
+/// ignored. Returns 0 if there are no results.
+#[must_use]
+pub fn balanced_accuracy<T>(results: &[Classification<T>]) -> f64
+where
+    T: Clone + Eq + Hash,
+{
+    let matrix = ConfusionMatrix::new(results);
+    let recalls: Vec<_> = matrix
+        .classes()
+        .iter()
+        .map(|class| matrix.class_metrics(class))
+        .filter(|metrics| metrics.support > 0)
+        .map(|metrics| metrics.recall)
+        .collect();
+
+    if recalls.is_empty() {
+        0.0
+    } else {
+        recalls.iter().sum::<f64>() / recalls.len() as f64
+    }
+}
+
+/// Summarizes the results of a classification algorithm as a table of
+/// the precision, recall, F1 score, and support of each classification,
+/// followed by the overall accuracy.
+#[must_use]
+pub fn classification_report<T>(results: &[Classification<T>]) -> String
+where
+    T: Clone + Display + Eq + Hash,
+{
+    let matrix = ConfusionMatrix::new(results);
+    let labels: Vec<_> = matrix.classes().iter().map(ToString::to_string).collect();
+    // the label column is as wide as the widest label
+    let width = labels
+        .iter()
+        .map(String::len)
+        .chain(["accuracy".len()])
+        .max()
+        .unwrap_or(0);
+
+    let mut report = format!(
+        "{:width$} {:>9} {:>9} {:>9} {:>9}\n",
+        "", "precision", "recall", "f1-score", "support"
+    );
+    for (class, label) in matrix.classes().iter().zip(&labels) {
+        let metrics = matrix.class_metrics(class);
+        report += &format!(
+            "{label:>width$} {:>9.2} {:>9.2} {:>9.2} {:>9}\n",
+            metrics.precision, metrics.recall, metrics.f1, metrics.support
+        );
+    }
+    report += &format!(
+        "\n{:>width$} {:>9} {:>9} {:>9.2} {:>9}",
+        "accuracy",
+        "",
+        "",
+        accuracy(results),
+        matrix.total()
+    );
+    report
+}
+
+/// Summarizes the results of a classification algorithm with
+/// [`classification_report`] if every test data point is labeled
+/// with a classification from the given training data.
+///
+/// Returns `None` for unlabeled test data, such as test data whose
+/// classifications are placeholders, since there is no accuracy to report.
+#[must_use]
+pub fn labeled_classification_report<T>(
+    train_data: &[DataPoint<T>],
+    results: &[Classification<T>],
+) -> Option<String>
+where
+    T: Clone + Display + Eq + Hash,
+{
+    let train_classes: HashSet<_> = train_data.iter().map(|d| &d.class).collect();
+    let labeled = results
+        .iter()
+        .all(|c| train_classes.contains(&c.data.class));
+    labeled.then(|| classification_report(results))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Point;
+
+    /// Creates data points with the given classifications.
+    fn data_points(classes: &[&'static str]) -> Vec<DataPoint<&'static str>> {
+        classes
+            .iter()
+            .map(|&class| DataPoint {
+                point: Point(vec![]),
+                class,
+                weight: 1.0,
+                id: None,
+            })
+            .collect()
+    }
+
+    /// Pairs data points with the given classification guesses.
+    fn results<'a>(
+        data: &'a [DataPoint<&'static str>],
+        guesses: &[&'static str],
+    ) -> Vec<Classification<'a, &'static str>> {
+        data.iter()
+            .zip(guesses)
+            .map(|(data, &class_guess)| Classification {
+                data,
+                class_guess,
+                confidence: None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn confusion_matrix() {
+        let data = data_points(&["red", "red", "red", "blue", "blue"]);
+        let results = results(&data, &["red", "blue", "red", "blue", "red"]);
+
+        let matrix = ConfusionMatrix::new(&results);
+        assert_eq!(matrix.classes(), ["red", "blue"]);
+        assert_eq!(matrix.get(&"red", &"red"), 2);
+        assert_eq!(matrix.get(&"red", &"blue"), 1);
+        assert_eq!(matrix.get(&"blue", &"red"), 1);
+        assert_eq!(matrix.get(&"blue", &"blue"), 1);
+        assert_eq!(matrix.get(&"green", &"red"), 0);
+        assert_eq!(matrix.total(), 5);
+
+        assert_eq!(
+            matrix.to_string(),
+            "      red blue\n red    2    1\nblue    1    1"
+        );
+    }
+
+    #[test]
+    fn precision_recall_f1() {
+        // red: 2 true positives, 1 false positive, 2 false negatives
+        let data = data_points(&["red", "red", "red", "red", "blue", "blue", "green"]);
+        let results = results(
+            &data,
+            &["red", "red", "blue", "green", "red", "blue", "green"],
+        );
+
+        let metrics = class_metrics(&results);
+        let red = metrics[&"red"];
+        assert!((red.precision - 2.0 / 3.0).abs() < 1e-12);
+        assert!((red.recall - 0.5).abs() < 1e-12);
+        assert!((red.f1 - 4.0 / 7.0).abs() < 1e-12);
+        assert_eq!(red.support, 4);
+
+        // green: 1 true positive, 1 false positive, 0 false negatives
+        let green = metrics[&"green"];
+        assert!((green.precision - 0.5).abs() < 1e-12);
+        assert!((green.recall - 1.0).abs() < 1e-12);
+        assert!((green.f1 - 2.0 / 3.0).abs() < 1e-12);
+        assert_eq!(green.support, 1);
+    }
+
+    #[test]
+    fn report() {
+        let data = data_points(&["red", "red", "red", "red", "blue", "blue", "green"]);
+        let results = results(
+            &data,
+            &["red", "red", "blue", "green", "red", "blue", "green"],
+        );
+
+        let report = classification_report(&results);
+        for class in ["red", "blue", "green"] {
+            assert!(report
+                .lines()
+                .any(|line| line.trim_start().starts_with(class)));
+        }
+        let accuracy_line = report.lines().last().unwrap();
+        assert!(accuracy_line.trim_start().starts_with("accuracy"));
+        assert!(accuracy_line.contains("0.57"));
+    }
+
+    #[test]
+    fn labeled_report() {
+        let train_data = data_points(&["red", "blue"]);
+        let data = data_points(&["red", "blue"]);
+        let labeled = results(&data, &["red", "red"]);
+        assert_eq!(
+            labeled_classification_report(&train_data, &labeled),
+            Some(classification_report(&labeled))
+        );
+
+        // test data with a classification not in the training data is unlabeled
+        let data = data_points(&["?", "?"]);
+        let unlabeled = results(&data, &["red", "blue"]);
+        assert_eq!(labeled_classification_report(&train_data, &unlabeled), None);
+    }
+
+    #[test]
+    fn balanced_accuracy_imbalanced() {
+        // always guessing the dominant classification
+        let mut classes = vec!["red"; 9];
+        classes.push("blue");
+        let data = data_points(&classes);
+        let results = results(&data, &["red"; 10]);
+
+        assert!((accuracy(&results) - 0.9).abs() < 1e-12);
+        assert!((balanced_accuracy(&results) - 0.5).abs() < 1e-12);
+    }
+}
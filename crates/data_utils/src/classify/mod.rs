@@ -4,11 +4,18 @@
 //! Author: Benjamin Hall
 
 pub mod bayes;
+pub mod kdtree;
+pub mod kmeans;
 pub mod knn;
+pub mod metrics;
 pub mod perceptron;
+pub mod scaling;
+pub mod validation;
 
-use crate::{DataPoint, Debug};
-pub use {bayes::*, knn::*, perceptron::*};
+use crate::{DataPoint, Debug, Point};
+pub use {
+    bayes::*, kdtree::*, kmeans::*, knn::*, metrics::*, perceptron::*, scaling::*, validation::*,
+};
 
 /// Stores the result of a classification algorithm.
 #[derive(Copy, Clone, Debug)]
@@ -17,4 +24,175 @@ pub struct Classification<'a, T> {
     pub data: &'a DataPoint<T>,
     /// The classification algorithm's guess for the data element's classification
     pub class_guess: T,
+    /// The classification algorithm's confidence in its guess, from 0 to 1,
+    /// if the algorithm provides one
+    pub confidence: Option<f64>,
+}
+
+impl<T> Classification<'_, T> {
+    /// Gets the identifier of the classified data point, if it has one.
+    #[inline]
+    #[must_use]
+    pub fn id(&self) -> Option<&str> {
+        self.data.id.as_deref()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T> serde::Serialize for Classification<'_, T>
+where
+    T: serde::Serialize,
+{
+    /// Serializes the classification as the point, its classification,
+    /// the guess, the confidence, and the identifier of the data point.
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+
+        let mut state = serializer.serialize_struct("Classification", 5)?;
+        state.serialize_field("point", &self.data.point)?;
+        state.serialize_field("class", &self.data.class)?;
+        state.serialize_field("class_guess", &self.class_guess)?;
+        state.serialize_field("confidence", &self.confidence)?;
+        state.serialize_field("id", &self.data.id)?;
+        state.end()
+    }
+}
+
+/// A classification algorithm that is trained on data
+/// and then used to classify points.
+pub trait Classifier<T> {
+    /// Trains the classifier on the given training data,
+    /// replacing any previous training.
+    fn train(&mut self, train_data: &[DataPoint<T>]);
+
+    /// Predicts the classification of a point.
+    fn predict(&self, point: &Point) -> T;
+}
+
+/// Stores the result of a regression algorithm.
+#[derive(Copy, Clone, Debug)]
+pub struct Regression<'a, T> {
+    /// The data point whose value has been predicted
+    pub data: &'a DataPoint<T>,
+    /// The regression algorithm's prediction of the data element's value
+    pub prediction: f64,
+}
+
+/// Converts log-scale scores to probabilities that sum to 1.
+///
+/// The maximum score is subtracted before exponentiating (the log-sum-exp
+/// trick), so large-magnitude scores do not overflow to infinity.
+fn softmax(scores: &[f64]) -> Vec<f64> {
+    let max = scores.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    if !max.is_finite() {
+        // no score can be normalized against, treat all scores as equal
+        let cnt = scores.len() as f64;
+        return vec![cnt.recip(); scores.len()];
+    }
+
+    let exps: Vec<_> = scores.iter().map(|s| (s - max).exp()).collect();
+    let total: f64 = exps.iter().sum();
+    exps.into_iter().map(|e| e / total).collect()
+}
+
+/// Picks the classification with the maximum log-scale score, returning
+/// it with the softmax of the scores as the confidence of the guess.
+fn max_softmax<'a, T>(class_results: impl IntoIterator<Item = (&'a T, f64)>) -> (T, Option<f64>)
+where
+    T: 'a + Clone + Default,
+{
+    let (classes, scores): (Vec<_>, Vec<_>) = class_results.into_iter().unzip();
+    // find the maximum classification value, pull out class and its probability
+    classes
+        .into_iter()
+        .zip(&scores)
+        .zip(softmax(&scores))
+        .max_by(|a, b| a.0 .1.total_cmp(b.0 .1))
+        .map_or_else(
+            || (T::default(), None),
+            |((class, _), p)| (class.clone(), Some(p)),
+        )
+}
+
+/// Calculates the fraction of the classification results
+/// whose guess matches the data's actual classification.
+///
+/// Returns 0 if there are no results.
+#[must_use]
+pub fn accuracy<T>(results: &[Classification<T>]) -> f64
+where
+    T: PartialEq,
+{
+    if results.is_empty() {
+        return 0.0;
+    }
+
+    let correct = results
+        .iter()
+        .filter(|c| c.class_guess == c.data.class)
+        .count();
+    correct as f64 / results.len() as f64
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Complex;
+
+    #[test]
+    fn accuracy_of_results() {
+        let data: Vec<_> = ["red", "red", "blue", "blue"]
+            .into_iter()
+            .map(|class| DataPoint {
+                point: Point(vec![]),
+                class,
+                weight: 1.0,
+                id: None,
+            })
+            .collect();
+        let results: Vec<_> = data
+            .iter()
+            .zip(["red", "blue", "blue", "blue"])
+            .map(|(data, class_guess)| Classification {
+                data,
+                class_guess,
+                confidence: None,
+            })
+            .collect();
+
+        assert!((accuracy(&results) - 0.75).abs() < f64::EPSILON);
+        assert!(accuracy::<&str>(&[]).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn boxed_classifiers() {
+        let train_data: Vec<_> = [(0.0, "red"), (1.0, "red"), (9.0, "blue"), (10.0, "blue")]
+            .into_iter()
+            .map(|(x, class)| DataPoint {
+                point: Point(vec![Complex::from(x)]),
+                class: class.to_owned(),
+                weight: 1.0,
+                id: None,
+            })
+            .collect();
+
+        let config = PerceptronConfig {
+            seed: Some(0),
+            max_epochs: 100,
+            ..PerceptronConfig::default()
+        };
+        let mut classifiers: Vec<Box<dyn Classifier<String>>> = vec![
+            Box::new(KNearestNeighbor::new(1)),
+            Box::new(BayesModel::new()),
+            Box::new(Perceptron::new(config)),
+        ];
+        for classifier in &mut classifiers {
+            classifier.train(&train_data);
+            assert_eq!(classifier.predict(&Point(vec![0.5.into()])), "red");
+            assert_eq!(classifier.predict(&Point(vec![9.5.into()])), "blue");
+        }
+    }
 }
@@ -3,22 +3,430 @@
 //!
 //! Author: Benjamin Hall
 
-use std::{collections::HashSet, hash::Hash};
+use std::{collections::HashSet, error::Error, hash::Hash};
 
-use super::Classification;
-use crate::{DataPoint, Point};
+use super::{max_softmax, softmax, Classification, Classifier};
+use crate::{dataset::weighted_mean, DataPoint, Point};
+
+/// The maximum number of passes over the training data by default.
+/// Training for longer may overtrain, or loop forever if the weights
+/// cannot converge.
+const MAX_EPOCHS: usize = 10_000;
+
+/// Configures the training of a Single-Layer Perceptron.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PerceptronConfig {
+    /// The amount each misclassification adjusts the weights
+    pub learning_rate: f64,
+    /// The fraction of the training data that can be
+    /// misclassified before training stops early
+    pub threshold: f64,
+    /// The maximum number of passes over the training data
+    pub max_epochs: usize,
+    /// The number of data points whose adjustments are
+    /// accumulated before the weights are updated
+    pub batch_size: usize,
+    /// The L2 regularization strength, which shrinks the
+    /// weights by `1 - l2 * learning_rate` each update
+    pub l2: f64,
+    /// The seed for the random weights and shuffling,
+    /// or `None` to use a random seed
+    pub seed: Option<u64>,
+    /// Whether to use the average of the weights across all
+    /// training steps rather than the final weights
+    pub averaged: bool,
+}
+
+impl Default for PerceptronConfig {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            learning_rate: 1.0,
+            threshold: 0.0,
+            max_epochs: MAX_EPOCHS,
+            batch_size: 1,
+            l2: 0.0,
+            seed: None,
+            averaged: false,
+        }
+    }
+}
 
 /// Generates a Point of random weights in the range -1.0..1.0.
 #[inline]
 #[must_use]
-fn generate_random_weights(size: usize) -> Point {
+fn generate_random_weights(rng: &mut fastrand::Rng, size: usize) -> Point {
     Point(
         (0..size)
-            .map(|_| fastrand::f64().mul_add(2.0, -1.0).into())
+            .map(|_| rng.f64().mul_add(2.0, -1.0).into())
             .collect(),
     )
 }
 
+/// Calculates the mean of the points of the given data,
+/// weighted by their sample weights.
+#[inline]
+#[must_use]
+fn mean_point<T>(data: &[DataPoint<T>]) -> Point {
+    weighted_mean(data.iter().map(|d| (&d.point, d.weight)))
+}
+
+/// Offsets a point by the mean and maps it to `[1, x]`.
+#[inline]
+#[must_use]
+fn augment(point: &Point, mean: &Point) -> Point {
+    // offset point by the mean
+    let mut point = point - mean;
+    // y = [1, x]
+    point.0.insert(0, 1.0.into());
+    point
+}
+
+/// Accumulates the weight adjustments of a batch of data for the
+/// weights of multiple classifications, and the running sum of the
+/// weights for the averaged perceptron.
+struct BatchTrainer<'a> {
+    /// The training configuration
+    config: &'a PerceptronConfig,
+    /// The sum of the adjustments to each classification's weights in the batch
+    adjustments: Vec<Option<Point>>,
+    /// The running sum of each classification's weights after each batch
+    weights_sums: Vec<Point>,
+    /// The number of batches in the running sums
+    steps: usize,
+}
+
+impl<'a> BatchTrainer<'a> {
+    /// Creates a trainer for the given initial weights.
+    fn new(weights: &[Point], config: &'a PerceptronConfig) -> Self {
+        Self {
+            config,
+            adjustments: vec![None; weights.len()],
+            weights_sums: weights
+                .iter()
+                .map(|w| Point(vec![0.0.into(); w.0.len()]))
+                .collect(),
+            steps: 0,
+        }
+    }
+
+    /// Accumulates an adjustment of the given classification's
+    /// weights by the point scaled by the given error.
+    fn adjust(&mut self, class: usize, point: &Point, error: f64) {
+        // scale point by the error and learning rate
+        let mut weight_adjustment = point.clone().scale(error * self.config.learning_rate);
+        // also take conjugate of complex numbers
+        for weight in &mut weight_adjustment.0 {
+            *weight = weight.conjugate();
+        }
+        match &mut self.adjustments[class] {
+            Some(adjustment) => *adjustment += weight_adjustment,
+            adjustment @ None => *adjustment = Some(weight_adjustment),
+        }
+    }
+
+    /// Updates the weights with the adjustments of the batch at the end of a batch.
+    fn update(&mut self, weights: &mut [Point]) {
+        let shrink = self.config.l2.mul_add(-self.config.learning_rate, 1.0);
+        for (w, adjustment) in weights.iter_mut().zip(&mut self.adjustments) {
+            if let Some(adjustment) = adjustment.take() {
+                // shrink weights for L2 regularization, then update weights
+                *w = w.clone().scale(shrink);
+                *w += adjustment;
+            }
+        }
+
+        if self.config.averaged {
+            for (sum, w) in self.weights_sums.iter_mut().zip(weights.iter()) {
+                *sum += w;
+            }
+            self.steps += 1;
+        }
+    }
+
+    /// Replaces the weights with their averages if training the averaged perceptron.
+    fn finish(self, weights: &mut [Point]) {
+        if self.config.averaged && self.steps > 0 {
+            for (w, sum) in weights.iter_mut().zip(self.weights_sums) {
+                *w = sum.scale(1.0 / self.steps as f64);
+            }
+        }
+    }
+}
+
+/// A trained binary Single-Layer Perceptron.
+#[derive(Clone, Debug)]
+pub struct PerceptronModel<T> {
+    /// The weights of the linear classifier, `[w_0, w]`
+    pub weights: Point,
+    /// The classification of points where `g(x) > 0`
+    pub pos_class: T,
+    /// The classification of points where `g(x) <= 0`
+    pub neg_class: T,
+    /// The mean of the training data, used to offset points
+    pub train_mean: Point,
+    /// The number of misclassified training data points in each
+    /// pass over the training data during training
+    pub history: Vec<usize>,
+}
+
+impl<T> PerceptronModel<T>
+where
+    T: Clone + Eq,
+{
+    /// Trains a Single-Layer Perceptron on the given training
+    /// data with the given configuration.
+    ///
+    /// Training runs for at most `max_epochs` passes over the training
+    /// data, but stops early once the fraction of misclassified training
+    /// data in a pass is below the threshold.
+    ///
+    /// The weights are updated once per batch of `batch_size` data points,
+    /// so a batch size of 1 updates after every data point and a batch size
+    /// of the length of the training data is full-batch gradient descent.
+    ///
+    /// If `averaged` is set, the averaged perceptron is trained instead,
+    /// which generalizes better than the final weights alone.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the training data does not have exactly two classifications.
+    #[must_use]
+    pub fn train(train_data: &[DataPoint<T>], config: &PerceptronConfig) -> Self {
+        let mut rng = config
+            .seed
+            .map_or_else(fastrand::Rng::new, fastrand::Rng::with_seed);
+        // initialize random weights, [w_i0, w_i] = 1 + dimension of training data points
+        let mut weights = generate_random_weights(&mut rng, train_data[0].point.0.len() + 1);
+
+        // let the first training data point be class 1 (g(x) > 0)
+        let pos_class = &train_data[0].class;
+        // any other classifications will be treated as class 2 (g(x) < 0)
+        let neg_class = train_data
+            .iter()
+            .map(|d| &d.class)
+            .find(|&c| c != pos_class)
+            .expect("The data provided to the SLP algorithm does not have two classifications");
+
+        // any further classifications cannot be separated by a binary classifier
+        let mut extra_classes = Vec::new();
+        for class in train_data.iter().map(|d| &d.class) {
+            if class != pos_class && class != neg_class && !extra_classes.contains(&class) {
+                extra_classes.push(class);
+            }
+        }
+        assert!(
+            extra_classes.is_empty(),
+            "The data provided to the SLP algorithm has more than two classifications: \
+             {} in total",
+            extra_classes.len() + 2
+        );
+
+        // calculate the mean of the training data so we can offset data points
+        let train_mean = mean_point(train_data);
+
+        // map all the training data to [1, x]
+        let mut y: Vec<_> = train_data
+            .iter()
+            .map(|d| DataPoint {
+                point: augment(&d.point, &train_mean),
+                class: &d.class,
+                weight: d.weight,
+                id: None,
+            })
+            .collect();
+
+        // running sum of the weights after each training step for the averaged perceptron
+        let mut weights_sum = Point(vec![0.0.into(); weights.0.len()]);
+        let mut steps = 0;
+
+        // loop at most max_epochs times, otherwise we may overtrain
+        // or enter an infinite loop if the weights cannot converge
+        let mut history = Vec::new();
+        while history.len() < config.max_epochs {
+            // shuffle data set -- this prevents oscillations and overtraining
+            rng.shuffle(&mut y);
+
+            let mut misclassified = 0;
+
+            // perceptron iterative algorithm
+            for batch in y.chunks(config.batch_size.max(1)) {
+                // sum of the weight adjustments of the misclassified data in the batch
+                let mut batch_adjustment: Option<Point> = None;
+
+                for d in batch {
+                    // get linear classifier value using the dot product of the data point and the weights
+                    let lin_class_value = weights.dot(&d.point);
+                    // convert linear classifier value to binary classification: 1.0 or -1.0
+                    let class_guess_value = lin_class_value.re.signum();
+                    // convert actual class to binary classification: 1.0 or -1.0
+                    let class_value = if d.class == pos_class { 1.0 } else { -1.0 };
+                    // calculate error in classification
+                    let error = class_value - class_guess_value;
+                    // if error is non-zero, accumulate adjustment and add to misclassified count
+                    if error != 0.0 {
+                        misclassified += 1;
+
+                        // scale point by the error and sample weight
+                        let weight_error = d.point.clone().scale(error * d.weight);
+                        // scale by learning rate
+                        let mut weight_adjustment = weight_error.scale(config.learning_rate);
+                        // also take conjugate of complex numbers
+                        for weight in &mut weight_adjustment.0 {
+                            *weight = weight.conjugate();
+                        }
+                        match &mut batch_adjustment {
+                            Some(adjustment) => *adjustment += weight_adjustment,
+                            None => batch_adjustment = Some(weight_adjustment),
+                        }
+                    }
+                }
+
+                if let Some(adjustment) = batch_adjustment {
+                    // shrink weights for L2 regularization, then update weights
+                    weights = weights.scale(config.l2.mul_add(-config.learning_rate, 1.0));
+                    weights += adjustment;
+                }
+
+                if config.averaged {
+                    weights_sum += &weights;
+                    steps += 1;
+                }
+            }
+
+            history.push(misclassified);
+
+            // continue until no misclassifications
+            if (misclassified as f64) < (config.threshold * train_data.len() as f64) {
+                break;
+            }
+        }
+
+        if config.averaged && steps > 0 {
+            weights = weights_sum.scale(1.0 / steps as f64);
+        }
+
+        Self {
+            weights,
+            pos_class: pos_class.clone(),
+            neg_class: neg_class.clone(),
+            train_mean,
+            history,
+        }
+    }
+
+    /// Predicts the classification of a point.
+    #[inline]
+    #[must_use]
+    pub fn predict(&self, point: &Point) -> T {
+        self.predict_with_confidence(point).0
+    }
+
+    /// Predicts the classification of a point, along with the confidence
+    /// of the prediction from 0 to 1.
+    ///
+    /// The confidence is the normalized margin `|g(y)| / (‖w‖ ‖y‖)`, the
+    /// cosine of the angle between the weights and the augmented point.
+    #[must_use]
+    pub fn predict_with_confidence(&self, point: &Point) -> (T, f64) {
+        // run the SLP on this point
+        let point = augment(point, &self.train_mean);
+        let class_result = self.weights.dot(&point);
+
+        // normalize the margin by the magnitudes of the weights and point
+        let norm = self.weights.magnitude() * point.magnitude();
+        let confidence = if norm > 0.0 {
+            (class_result.re.abs() / norm).min(1.0)
+        } else {
+            0.0
+        };
+
+        // determine if positive or negative class
+        if class_result.re > 0.0 {
+            (self.pos_class.clone(), confidence)
+        } else {
+            (self.neg_class.clone(), confidence)
+        }
+    }
+}
+
+/// A binary Single-Layer Perceptron classifier.
+#[derive(Clone, Debug, Default)]
+pub struct Perceptron<T> {
+    /// The configuration used to train the perceptron
+    pub config: PerceptronConfig,
+    /// The trained model, if the perceptron has been trained
+    model: Option<PerceptronModel<T>>,
+}
+
+impl<T> Perceptron<T> {
+    /// Creates an untrained perceptron with the given configuration.
+    #[inline]
+    #[must_use]
+    pub const fn new(config: PerceptronConfig) -> Self {
+        Self {
+            config,
+            model: None,
+        }
+    }
+
+    /// Returns the trained model, if the perceptron has been trained.
+    #[inline]
+    #[must_use]
+    pub const fn model(&self) -> Option<&PerceptronModel<T>> {
+        self.model.as_ref()
+    }
+}
+
+impl<T> Classifier<T> for Perceptron<T>
+where
+    T: Clone + Default + Eq,
+{
+    #[inline]
+    fn train(&mut self, train_data: &[DataPoint<T>]) {
+        self.model = Some(PerceptronModel::train(train_data, &self.config));
+    }
+
+    #[inline]
+    fn predict(&self, point: &Point) -> T {
+        self.model
+            .as_ref()
+            .map_or_else(T::default, |model| model.predict(point))
+    }
+}
+
+/// Runs the Single-Layer Perceptron algorithm with the given training
+/// data on the given test data with the given configuration, such
+/// as to seed the random weights for reproducible results.
+///
+/// Note that this algorithm requires that the data can only be
+/// split into two classifications.
+#[must_use]
+pub fn single_layer_perceptron_with_config<'a, T>(
+    train_data: &[DataPoint<T>],
+    test_data: &'a [DataPoint<T>],
+    config: &PerceptronConfig,
+) -> Vec<Classification<'a, T>>
+where
+    T: Clone + Eq,
+{
+    let model = PerceptronModel::train(train_data, config);
+
+    // run SLP on all test data and collect the results
+    test_data
+        .iter()
+        .map(|data| {
+            let (class_guess, confidence) = model.predict_with_confidence(&data.point);
+            // wrap in a Classification
+            Classification {
+                data,
+                class_guess,
+                confidence: Some(confidence),
+            }
+        })
+        .collect()
+}
+
 /// Runs the Single-Layer Perceptron algorithm with the given training
 /// data on the given test data with the given learning rate.
 ///
@@ -34,228 +442,681 @@ pub fn single_layer_perceptron<'a, T>(
 where
     T: Clone + Eq,
 {
-    // initialize random weights, [w_i0, w_i] = 1 + dimension of training data points
-    let mut weights = generate_random_weights(train_data[0].point.0.len() + 1);
+    let config = PerceptronConfig {
+        learning_rate,
+        threshold,
+        ..PerceptronConfig::default()
+    };
+    single_layer_perceptron_with_config(train_data, test_data, &config)
+}
+
+/// Runs the averaged Single-Layer Perceptron algorithm with the given
+/// training data on the given test data with the given learning rate.
+///
+/// The averaged perceptron classifies using the average of the weights
+/// across all training steps, which is less sensitive to the last few
+/// updates than the final weights.
+///
+/// Note that this algorithm requires that the data can only be
+/// split into two classifications.
+#[must_use]
+pub fn averaged_single_layer_perceptron<'a, T>(
+    train_data: &[DataPoint<T>],
+    test_data: &'a [DataPoint<T>],
+    learning_rate: f64,
+    threshold: f64,
+) -> Vec<Classification<'a, T>>
+where
+    T: Clone + Eq,
+{
+    let config = PerceptronConfig {
+        learning_rate,
+        threshold,
+        averaged: true,
+        ..PerceptronConfig::default()
+    };
+    single_layer_perceptron_with_config(train_data, test_data, &config)
+}
+
+/// Runs the Multiclass Single-Layer Perceptron algorithm with the given
+/// training data on the given test data with the given learning rate.
+///
+/// This algorithm uses the one-vs-rest method to transform the multiclass
+/// problem to multiple binary classifications.
+///
+/// # Errors
+///
+/// Returns an error if there is no training data.
+#[inline]
+pub fn multiclass_single_layer_perceptron<'a, T>(
+    train_data: &[DataPoint<T>],
+    test_data: &'a [DataPoint<T>],
+    learning_rate: f64,
+    threshold: f64,
+) -> Result<Vec<Classification<'a, T>>, Box<dyn Error>>
+where
+    T: Clone + Default + Eq + Hash,
+{
+    let config = PerceptronConfig {
+        learning_rate,
+        threshold,
+        ..PerceptronConfig::default()
+    };
+    multiclass_single_layer_perceptron_with_config(train_data, test_data, &config)
+}
+
+/// Runs the Multiclass Single-Layer Perceptron algorithm with the given
+/// training data on the given test data with the given configuration.
+///
+/// As with [`PerceptronModel::train`], the weights of every classification
+/// are updated once per batch, with L2 regularization, and the averaged
+/// weights are used if `averaged` is set.
+///
+/// # Errors
+///
+/// Returns an error if there is no training data.
+pub fn multiclass_single_layer_perceptron_with_config<'a, T>(
+    train_data: &[DataPoint<T>],
+    test_data: &'a [DataPoint<T>],
+    config: &PerceptronConfig,
+) -> Result<Vec<Classification<'a, T>>, Box<dyn Error>>
+where
+    T: Clone + Default + Eq + Hash,
+{
+    let Some(first) = train_data.first() else {
+        return Err("No training data provided to the SLP algorithm".into());
+    };
 
-    // let the first training data point be class 1 (g(x) > 0)
-    let pos_class = &train_data[0].class;
-    // any other classifications will be treated as class 2 (g(x) < 0)
-    let neg_class = train_data
+    let mut rng = config
+        .seed
+        .map_or_else(fastrand::Rng::new, fastrand::Rng::with_seed);
+
+    // create list of classifications, in the order they first appear
+    // so the random weights do not depend on the iteration order
+    let mut seen = HashSet::new();
+    let classes: Vec<_> = train_data
         .iter()
         .map(|d| &d.class)
-        .find(|&c| c != pos_class)
-        .expect("The data provided to the SLP algorithm does not have two classifications");
+        .filter(|&class| seen.insert(class))
+        .collect();
 
-    // calculate the mean of the training data so we can offset data points
-    let train_mean = train_data
+    // initialize random weights for each classification, -1.0..1.0
+    let mut weights: Vec<_> = classes
         .iter()
-        .map(|d| &d.point)
-        .sum::<Point>()
-        .scale(1.0 / train_data.len() as f64);
+        .map(|_| generate_random_weights(&mut rng, first.point.0.len() + 1))
+        .collect();
 
-    // map all the training data to [1, x]
+    // calculate the mean of the training data so we can offset data points
+    let train_mean = mean_point(train_data);
+
+    // map all the training data to [1, x], with the index of the classification
     let mut y: Vec<_> = train_data
         .iter()
-        .map(|d| {
-            // offset training point by the mean
-            let mut point = &d.point - &train_mean;
-            // y = [1, x]
-            point.0.insert(0, 1.0.into());
-            DataPoint {
-                point,
-                class: &d.class,
-            }
+        .map(|d| DataPoint {
+            point: augment(&d.point, &train_mean),
+            class: classes.iter().position(|&c| c == &d.class).unwrap(),
+            weight: d.weight,
+            id: None,
         })
         .collect();
 
-    // loop at most 10,000 times, otherwise we may overtrain
+    let mut trainer = BatchTrainer::new(&weights, config);
+
+    // loop at most max_epochs times, otherwise we may overtrain
     // or enter an infinite loop if the weights cannot converge
-    for _ in 0..10_000 {
+    for _ in 0..config.max_epochs {
         // shuffle data set -- this prevents oscillations and overtraining
-        fastrand::shuffle(&mut y);
+        rng.shuffle(&mut y);
 
         let mut misclassified = 0;
 
         // perceptron iterative algorithm
-        for d in &y {
-            // get linear classifier value using the dot product of the data point and the weights
-            let lin_class_value = weights.dot(&d.point);
-            // convert linear classifier value to binary classification: 1.0 or -1.0
-            let class_guess_value = lin_class_value.re.signum();
-            // convert actual class to binary classification: 1.0 or -1.0
-            let class_value = if d.class == pos_class { 1.0 } else { -1.0 };
-            // calculate error in classification
-            let error = class_value - class_guess_value;
-            // if error is non-zero, adjust weights and add to misclassified count
-            if error != 0.0 {
-                misclassified += 1;
-
-                // scale point by the error
-                let weight_error = d.point.clone().scale(error);
-                // scale by learning rate
-                let mut weight_adjustment = weight_error.scale(learning_rate);
-                // also take conjugate of complex numbers
-                for weight in &mut weight_adjustment.0 {
-                    *weight = weight.conjugate();
+        for batch in y.chunks(config.batch_size.max(1)) {
+            for d in batch {
+                // get linear classifier values using the dot product of the data point and the weights,
+                // and find the maximum classification value
+                let class_guess = weights
+                    .iter()
+                    .map(|w| w.dot(&d.point).re)
+                    .enumerate()
+                    .max_by(|a, b| a.1.total_cmp(&b.1))
+                    .map(|(i, _)| i);
+                // if classification is wrong, adjust weights
+                if class_guess != Some(d.class) {
+                    misclassified += 1;
+
+                    for i in 0..weights.len() {
+                        // increase the weights of the correct class,
+                        // and decrease the weights of the incorrect classes
+                        let sign = if i == d.class { 1.0 } else { -1.0 };
+                        trainer.adjust(i, &d.point, sign * d.weight);
+                    }
                 }
-                // update weights
-                weights += weight_adjustment;
             }
+
+            trainer.update(&mut weights);
         }
 
         // continue until no misclassifications
-        if (misclassified as f64) < (threshold * train_data.len() as f64) {
+        if (misclassified as f64) < (config.threshold * train_data.len() as f64) {
             break;
         }
     }
+    trainer.finish(&mut weights);
 
     // run SLP on all test data and collect the results
-    test_data
+    Ok(test_data
         .iter()
         .map(|data| {
             // run the SLP on this data
-            let mut point = &data.point - &train_mean;
-            point.0.insert(0, 1.0.into());
-            let class_result = weights.dot(&point);
+            let point = augment(&data.point, &train_mean);
+
+            // find the maximum classification value, pull out class and its softmax
+            let (class_guess, confidence) = max_softmax(
+                classes
+                    .iter()
+                    .zip(&weights)
+                    .map(|(&class, w)| (class, w.dot(&point).re)),
+            );
 
-            // determine if positive or negative class
-            let class_guess = if class_result.re > 0.0 {
-                pos_class.clone()
-            } else {
-                neg_class.clone()
-            };
             // wrap in a Classification
-            Classification { data, class_guess }
+            Classification {
+                data,
+                class_guess,
+                confidence,
+            }
         })
-        .collect()
+        .collect())
 }
 
-/// Runs the Multiclass Single-Layer Perceptron algorithm with the given
-/// training data on the given test data with the given learning rate.
+/// Runs the Softmax Perceptron algorithm with the given training
+/// data on the given test data with the given learning rate.
 ///
-/// This algorithm uses the one-vs-rest method to transform the multiclass
-/// problem to multiple binary classifications.
+/// Unlike the one-vs-rest method, this algorithm trains the weights of all
+/// classifications together, using a softmax over the linear classifier
+/// values and cross-entropy updates. This handles overlapping
+/// classifications better than one-vs-rest.
+#[inline]
 #[must_use]
-pub fn multiclass_single_layer_perceptron<'a, T>(
+pub fn softmax_perceptron<'a, T>(
     train_data: &[DataPoint<T>],
     test_data: &'a [DataPoint<T>],
     learning_rate: f64,
     threshold: f64,
 ) -> Vec<Classification<'a, T>>
 where
-    T: Clone + Default + Eq + Hash,
+    T: Clone + Default + Eq,
+{
+    let config = PerceptronConfig {
+        learning_rate,
+        threshold,
+        ..PerceptronConfig::default()
+    };
+    softmax_perceptron_with_config(train_data, test_data, &config)
+}
+
+/// Runs the Softmax Perceptron algorithm with the given training
+/// data on the given test data with the given configuration.
+///
+/// As with [`PerceptronModel::train`], the weights of every classification
+/// are updated once per batch, with L2 regularization, and the averaged
+/// weights are used if `averaged` is set.
+#[must_use]
+pub fn softmax_perceptron_with_config<'a, T>(
+    train_data: &[DataPoint<T>],
+    test_data: &'a [DataPoint<T>],
+    config: &PerceptronConfig,
+) -> Vec<Classification<'a, T>>
+where
+    T: Clone + Default + Eq,
 {
-    /// Stores weights for a classification.
-    struct Weights<'a, T> {
-        class: &'a T,
-        /// w = 2µ
-        w: Point,
-    }
-
-    let mut weights_vec: Vec<_> = {
-        // create set of classifications
-        let mut classes = HashSet::new();
-        for d in train_data {
-            classes.insert(&d.class);
+    let mut rng = config
+        .seed
+        .map_or_else(fastrand::Rng::new, fastrand::Rng::with_seed);
+
+    // create list of classifications
+    let mut classes = Vec::new();
+    for d in train_data {
+        if !classes.contains(&&d.class) {
+            classes.push(&d.class);
         }
+    }
 
-        // initialize random weights for each classification, -1.0..1.0
-        classes
-            .into_iter()
-            .map(|class| Weights {
-                class,
-                w: generate_random_weights(test_data[0].point.0.len() + 1),
-            })
-            .collect()
-    };
+    // initialize random weights for each classification, -1.0..1.0
+    let dim = train_data.first().map_or(0, |d| d.point.0.len());
+    let mut weights: Vec<_> = classes
+        .iter()
+        .map(|_| generate_random_weights(&mut rng, dim + 1))
+        .collect();
 
     // calculate the mean of the training data so we can offset data points
-    let train_mean = train_data
-        .iter()
-        .map(|d| &d.point)
-        .sum::<Point>()
-        .scale(1.0 / train_data.len() as f64);
+    let train_mean = mean_point(train_data);
 
-    // map all the training data to [1, x]
+    // map all the training data to [1, x], with the index of the classification
     let mut y: Vec<_> = train_data
         .iter()
-        .map(|d| {
-            // offset training point by the mean
-            let mut point = &d.point - &train_mean;
-            // y = [1, x]
-            point.0.insert(0, 1.0.into());
-            DataPoint {
-                point,
-                class: &d.class,
-            }
+        .map(|d| DataPoint {
+            point: augment(&d.point, &train_mean),
+            class: classes.iter().position(|&c| c == &d.class).unwrap(),
+            weight: d.weight,
+            id: None,
         })
         .collect();
 
-    // loop at most 10,000 times, otherwise we may overtrain
+    let mut trainer = BatchTrainer::new(&weights, config);
+
+    // loop at most max_epochs times, otherwise we may overtrain
     // or enter an infinite loop if the weights cannot converge
-    for _ in 0..10_000 {
+    for _ in 0..config.max_epochs {
         // shuffle data set -- this prevents oscillations and overtraining
-        fastrand::shuffle(&mut y);
+        rng.shuffle(&mut y);
 
         let mut misclassified = 0;
 
-        // perceptron iterative algorithm
-        for d in &y {
-            // get linear classifier values using the dot product of the data point and the weights
-            let class_results = weights_vec
-                .iter()
-                .map(|weights| (weights.class, weights.w.dot(&d.point)));
-            // find the maximum classification value, pull out class
-            let class_guess = class_results
-                .max_by(|a, b| a.1.re.total_cmp(&b.1.re))
-                .map(|(class, _)| class)
-                .unwrap();
-            // if classification is wrong, adjust weights
-            if class_guess != d.class {
-                misclassified += 1;
-
-                // scale point by learning rate
-                let mut weight_adjustment = d.point.clone().scale(learning_rate);
-                // also take conjugate of complex numbers
-                for weight in &mut weight_adjustment.0 {
-                    *weight = weight.conjugate();
+        for batch in y.chunks(config.batch_size.max(1)) {
+            for d in batch {
+                // get linear classifier values using the dot product of the data point and the weights
+                let scores: Vec<_> = weights.iter().map(|w| w.dot(&d.point).re).collect();
+                // the classification guess is the class with the maximum value
+                let class_guess = scores
+                    .iter()
+                    .enumerate()
+                    .max_by(|a, b| a.1.total_cmp(b.1))
+                    .map(|(i, _)| i);
+                if class_guess != Some(d.class) {
+                    misclassified += 1;
                 }
-                // update weights
-                for weights in &mut weights_vec {
-                    if weights.class == d.class {
-                        // increase the weights of the correct class
-                        weights.w += &weight_adjustment;
-                    } else {
-                        // decrease the weights of the incorrect classes
-                        weights.w -= &weight_adjustment;
-                    }
+
+                // adjust the weights of every class by the error in its probability
+                for (i, p) in softmax(&scores).into_iter().enumerate() {
+                    let target = if i == d.class { 1.0 } else { 0.0 };
+                    trainer.adjust(i, &d.point, (target - p) * d.weight);
                 }
             }
+
+            trainer.update(&mut weights);
         }
 
         // continue until no misclassifications
-        if (misclassified as f64) < (threshold * train_data.len() as f64) {
+        if (misclassified as f64) < (config.threshold * train_data.len() as f64) {
             break;
         }
     }
+    trainer.finish(&mut weights);
 
-    // run SLP on all test data and collect the results
+    // run the perceptron on all test data and collect the results
     test_data
         .iter()
         .map(|data| {
-            // run the SLP on this data
-            let mut point = &data.point - &train_mean;
-            point.0.insert(0, 1.0.into());
+            let point = augment(&data.point, &train_mean);
 
-            // find the maximum classification value, pull out class
-            let class_guess = weights_vec
-                .iter()
-                .map(|weights| (weights.class, weights.w.dot(&point)))
-                .max_by(|a, b| a.1.re.total_cmp(&b.1.re))
-                .map_or_else(T::default, |(class, _)| class.clone());
+            // find the maximum classification value, pull out class and its probability
+            let (class_guess, confidence) = max_softmax(
+                classes
+                    .iter()
+                    .zip(&weights)
+                    .map(|(&class, w)| (class, w.dot(&point).re)),
+            );
 
             // wrap in a Classification
-            Classification { data, class_guess }
+            Classification {
+                data,
+                class_guess,
+                confidence,
+            }
         })
         .collect()
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Creates a labeled two-dimensional data point.
+    fn data_point(x: f64, y: f64, class: &str) -> DataPoint<String> {
+        DataPoint {
+            point: Point(vec![x.into(), y.into()]),
+            class: class.to_owned(),
+            weight: 1.0,
+            id: None,
+        }
+    }
+
+    /// Creates a linearly separable set of training data.
+    fn separable_data() -> Vec<DataPoint<String>> {
+        vec![
+            data_point(0.0, 0.0, "red"),
+            data_point(1.0, 0.0, "red"),
+            data_point(0.0, 1.0, "red"),
+            data_point(1.0, 1.0, "red"),
+            data_point(4.0, 4.0, "blue"),
+            data_point(5.0, 4.0, "blue"),
+            data_point(4.0, 5.0, "blue"),
+            data_point(5.0, 5.0, "blue"),
+        ]
+    }
+
+    /// Creates a set of training data with overlapping classes.
+    fn noisy_data() -> Vec<DataPoint<String>> {
+        let mut rng = fastrand::Rng::with_seed(0);
+        (0..100)
+            .map(|i| {
+                let (offset, class) = if i % 2 == 0 {
+                    (0.0, "red")
+                } else {
+                    (1.0, "blue")
+                };
+                data_point(
+                    offset + rng.f64().mul_add(4.0, -2.0),
+                    offset + rng.f64().mul_add(4.0, -2.0),
+                    class,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn perceptron_model() {
+        let model = PerceptronModel::train(&separable_data(), &PerceptronConfig::default());
+        assert_eq!(model.predict(&Point(vec![0.5.into(), 0.5.into()])), "red");
+        assert_eq!(model.predict(&Point(vec![4.5.into(), 4.5.into()])), "blue");
+    }
+
+    #[test]
+    fn perceptron_max_epochs() {
+        // XOR cannot be separated, so training never converges
+        let train_data = [
+            data_point(0.0, 0.0, "red"),
+            data_point(1.0, 1.0, "red"),
+            data_point(0.0, 1.0, "blue"),
+            data_point(1.0, 0.0, "blue"),
+        ];
+
+        let config = PerceptronConfig {
+            max_epochs: 1,
+            ..PerceptronConfig::default()
+        };
+        let model = PerceptronModel::train(&train_data, &config);
+        assert_eq!(model.history.len(), 1);
+
+        let config = PerceptronConfig {
+            max_epochs: 5,
+            ..PerceptronConfig::default()
+        };
+        let model = PerceptronModel::train(&train_data, &config);
+        assert_eq!(model.history.len(), 5);
+    }
+
+    #[test]
+    fn perceptron_seed() {
+        let config = PerceptronConfig {
+            seed: Some(42),
+            ..PerceptronConfig::default()
+        };
+        let a = PerceptronModel::train(&separable_data(), &config);
+        let b = PerceptronModel::train(&separable_data(), &config);
+        assert_eq!(a.weights, b.weights);
+        assert_eq!(a.history, b.history);
+    }
+
+    #[test]
+    fn perceptron_history() {
+        // stop as soon as an epoch has no misclassifications
+        let config = PerceptronConfig {
+            threshold: 1e-9,
+            seed: Some(0),
+            ..PerceptronConfig::default()
+        };
+        let model = PerceptronModel::train(&separable_data(), &config);
+
+        // training continues until there are no misclassifications
+        let (&last, rest) = model.history.split_last().unwrap();
+        assert_eq!(last, 0);
+        assert!(rest.iter().all(|&m| m > 0));
+    }
+
+    #[test]
+    fn averaged_perceptron() {
+        let train_data = separable_data();
+        let classifications = averaged_single_layer_perceptron(&train_data, &train_data, 1.0, 0.0);
+        for c in classifications {
+            assert_eq!(c.class_guess, c.data.class);
+        }
+
+        // overlapping classes never converge, so the final weights depend on the last updates
+        let noisy_data = noisy_data();
+
+        // measures how much the direction of the decision boundary varies between seeds
+        let spread = |averaged| {
+            let directions: Vec<_> = (0..10)
+                .map(|seed| {
+                    let config = PerceptronConfig {
+                        max_epochs: 50,
+                        seed: Some(seed),
+                        averaged,
+                        ..PerceptronConfig::default()
+                    };
+                    let weights = PerceptronModel::train(&noisy_data, &config).weights;
+                    weights.0[2].re.atan2(weights.0[1].re)
+                })
+                .collect();
+            let mean = directions.iter().sum::<f64>() / directions.len() as f64;
+            directions.iter().map(|d| (d - mean).powi(2)).sum::<f64>()
+        };
+        assert!(spread(true) < spread(false));
+    }
+
+    #[test]
+    fn perceptron_l2() {
+        // overlapping classes never converge, so the weights keep being updated
+        let train_data = noisy_data();
+
+        // the weights fluctuate between updates, so compare the total over several seeds
+        let total_magnitude = |l2| {
+            (0..10)
+                .map(|seed| {
+                    let config = PerceptronConfig {
+                        max_epochs: 100,
+                        l2,
+                        seed: Some(seed),
+                        ..PerceptronConfig::default()
+                    };
+                    PerceptronModel::train(&train_data, &config)
+                        .weights
+                        .magnitude()
+                })
+                .sum::<f64>()
+        };
+        assert!(total_magnitude(0.3) < total_magnitude(0.0));
+    }
+
+    #[test]
+    #[should_panic(expected = "more than two classifications: 3 in total")]
+    fn perceptron_three_classes() {
+        let mut train_data = separable_data();
+        train_data.push(data_point(10.0, 0.0, "green"));
+        let _ = PerceptronModel::train(&train_data, &PerceptronConfig::default());
+    }
+
+    #[test]
+    fn softmax_perceptron_three_classes() {
+        // three overlapping clusters
+        let mut rng = fastrand::Rng::with_seed(0);
+        let train_data: Vec<_> = (0..90)
+            .map(|i| {
+                let (x, y, class) = match i % 3 {
+                    0 => (0.0, 0.0, "red"),
+                    1 => (4.0, 0.0, "green"),
+                    _ => (2.0, 4.0, "blue"),
+                };
+                data_point(
+                    x + rng.f64().mul_add(3.0, -1.5),
+                    y + rng.f64().mul_add(3.0, -1.5),
+                    class,
+                )
+            })
+            .collect();
+
+        let config = PerceptronConfig {
+            learning_rate: 0.1,
+            threshold: 0.05,
+            seed: Some(0),
+            ..PerceptronConfig::default()
+        };
+        let classifications = softmax_perceptron_with_config(&train_data, &train_data, &config);
+        let correct = classifications
+            .iter()
+            .filter(|c| c.class_guess == c.data.class)
+            .count();
+        assert!(correct as f64 / classifications.len() as f64 > 0.9);
+    }
+
+    #[test]
+    fn multiclass_perceptron_seed() {
+        let mut train_data = noisy_data();
+        train_data.push(data_point(10.0, 0.0, "green"));
+        let config = PerceptronConfig {
+            max_epochs: 20,
+            seed: Some(42),
+            ..PerceptronConfig::default()
+        };
+
+        // the same seed produces the same weights, and so the same confidences
+        let guesses = |c: &[Classification<String>]| -> Vec<_> {
+            c.iter()
+                .map(|c| (c.class_guess.clone(), c.confidence))
+                .collect()
+        };
+        let a = multiclass_single_layer_perceptron_with_config(&train_data, &train_data, &config)
+            .unwrap();
+        let b = multiclass_single_layer_perceptron_with_config(&train_data, &train_data, &config)
+            .unwrap();
+        assert_eq!(guesses(&a), guesses(&b));
+
+        let a = softmax_perceptron_with_config(&train_data, &train_data, &config);
+        let b = softmax_perceptron_with_config(&train_data, &train_data, &config);
+        assert_eq!(guesses(&a), guesses(&b));
+    }
+
+    #[test]
+    fn multiclass_perceptron_config() {
+        let mut train_data = separable_data();
+        train_data.push(data_point(10.0, 0.0, "green"));
+        train_data.push(data_point(11.0, 1.0, "green"));
+        let config = PerceptronConfig {
+            seed: Some(7),
+            ..PerceptronConfig::default()
+        };
+        let guesses = |c: &[Classification<String>]| -> Vec<_> {
+            c.iter()
+                .map(|c| (c.class_guess.clone(), c.confidence))
+                .collect()
+        };
+
+        // batching, L2 regularization, and averaging all change the trained weights
+        let plain =
+            multiclass_single_layer_perceptron_with_config(&train_data, &train_data, &config)
+                .unwrap();
+        let softmax_plain = softmax_perceptron_with_config(&train_data, &train_data, &config);
+        for tuned in [
+            PerceptronConfig {
+                batch_size: 4,
+                ..config
+            },
+            PerceptronConfig { l2: 0.1, ..config },
+            PerceptronConfig {
+                averaged: true,
+                ..config
+            },
+        ] {
+            let c =
+                multiclass_single_layer_perceptron_with_config(&train_data, &train_data, &tuned)
+                    .unwrap();
+            assert_ne!(guesses(&c), guesses(&plain));
+            assert!(c.iter().all(|c| c.class_guess == c.data.class));
+
+            let c = softmax_perceptron_with_config(&train_data, &train_data, &tuned);
+            assert_ne!(guesses(&c), guesses(&softmax_plain));
+        }
+    }
+
+    #[test]
+    fn multiclass_perceptron_empty_data() {
+        let train_data = separable_data();
+        let classifications =
+            multiclass_single_layer_perceptron(&train_data, &[], 1.0, 0.0).unwrap();
+        assert!(classifications.is_empty());
+
+        let test_data = separable_data();
+        assert!(multiclass_single_layer_perceptron(&[], &test_data, 1.0, 0.0).is_err());
+    }
+
+    #[test]
+    fn perceptron_batch_size() {
+        // batch training still converges on separable data
+        let config = PerceptronConfig {
+            threshold: 1e-9,
+            batch_size: 3,
+            seed: Some(0),
+            ..PerceptronConfig::default()
+        };
+        let model = PerceptronModel::train(&separable_data(), &config);
+        assert_eq!(model.history.last(), Some(&0));
+
+        // a batch of all the data updates once per epoch using the initial weights
+        let train_data = separable_data();
+        let config = PerceptronConfig {
+            max_epochs: 1,
+            batch_size: train_data.len(),
+            seed: Some(0),
+            ..PerceptronConfig::default()
+        };
+        let model = PerceptronModel::train(&train_data, &config);
+
+        let mut rng = fastrand::Rng::with_seed(0);
+        let mut expected = generate_random_weights(&mut rng, 3);
+        let mean = mean_point(&train_data);
+        let mut adjustment = Point(vec![0.0.into(); 3]);
+        for d in &train_data {
+            let point = augment(&d.point, &mean);
+            let class_value = if d.class == model.pos_class {
+                1.0
+            } else {
+                -1.0
+            };
+            let error = class_value - expected.dot(&point).re.signum();
+            adjustment += point.scale(error);
+        }
+        expected += adjustment;
+
+        for (w, e) in model.weights.0.iter().zip(&expected.0) {
+            assert!((w.re - e.re).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn perceptron_sample_weights() {
+        let mut train_data = separable_data();
+        let unweighted = PerceptronModel::train(&train_data, &PerceptronConfig::default());
+        assert_eq!(unweighted.train_mean, Point(vec![2.5.into(), 2.5.into()]));
+
+        // a heavily weighted point pulls the mean towards it
+        train_data[0].weight = 9.0;
+        let weighted = PerceptronModel::train(&train_data, &PerceptronConfig::default());
+        assert_eq!(weighted.train_mean, Point(vec![1.25.into(), 1.25.into()]));
+        assert_eq!(mean_point(&train_data), weighted.train_mean);
+    }
+
+    #[test]
+    fn perceptron_confidence() {
+        let train_data = separable_data();
+        let classifications = single_layer_perceptron(&train_data, &train_data, 1.0, 0.0);
+        let multiclass =
+            multiclass_single_layer_perceptron(&train_data, &train_data, 1.0, 0.0).unwrap();
+        let softmax = softmax_perceptron(&train_data, &train_data, 1.0, 0.0);
+        for c in classifications.iter().chain(&multiclass).chain(&softmax) {
+            let confidence = c.confidence.unwrap();
+            assert!((0.0..=1.0).contains(&confidence));
+        }
+    }
+}
@@ -0,0 +1,236 @@
+//! This module provides interfaces to scale
+//! data before running classification algorithms.
+//!
+//! Author: Benjamin Hall
+
+use crate::{Complex, DataPoint, Point};
+
+/// Standardizes data to zero mean and unit standard deviation
+/// in each dimension, i.e. z-score normalization.
+///
+/// The same fitted parameters should be used to transform both
+/// the training data and the test data.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Standardizer {
+    /// The mean of each dimension
+    pub mean: Point,
+    /// The standard deviation of each dimension
+    pub std_dev: Vec<f64>,
+}
+
+impl Standardizer {
+    /// Calculates the mean and standard deviation of each
+    /// dimension of the given data.
+    #[must_use]
+    pub fn fit<T>(data: &[DataPoint<T>]) -> Self {
+        let cnt = data.len() as f64;
+        // sum together points and scale by 1/cnt
+        let mean = data
+            .iter()
+            .map(|d| &d.point)
+            .sum::<Point>()
+            .scale(cnt.recip());
+
+        // sum the squared magnitudes of the offsets from the mean
+        let mut variance = vec![0.0; mean.0.len()];
+        for d in data {
+            let offset = &d.point - &mean;
+            for (v, x) in variance.iter_mut().zip(&offset.0) {
+                *v += (*x * x.conjugate()).re;
+            }
+        }
+        let std_dev = variance.into_iter().map(|v| (v / cnt).sqrt()).collect();
+
+        Self { mean, std_dev }
+    }
+
+    /// Standardizes a point using the fitted mean and standard deviation.
+    ///
+    /// Dimensions with zero standard deviation are only offset by the mean,
+    /// and any dimensions that were not fitted are left unchanged.
+    #[must_use]
+    pub fn transform(&self, point: &Point) -> Point {
+        let mut point = point - &self.mean;
+        for (x, &std_dev) in point.0.iter_mut().zip(&self.std_dev) {
+            if std_dev != 0.0 {
+                *x = x.scale(std_dev.recip());
+            }
+        }
+        point
+    }
+
+    /// Standardizes a data point using the fitted mean and standard deviation.
+    #[inline]
+    #[must_use]
+    pub fn transform_point<T>(&self, data: &DataPoint<T>) -> DataPoint<T>
+    where
+        T: Clone,
+    {
+        DataPoint {
+            point: self.transform(&data.point),
+            class: data.class.clone(),
+            weight: data.weight,
+            id: data.id.clone(),
+        }
+    }
+
+    /// Fits a standardizer to the given data, returning the
+    /// standardizer and the standardized data.
+    #[must_use]
+    pub fn fit_transform<T>(data: &[DataPoint<T>]) -> (Self, Vec<DataPoint<T>>)
+    where
+        T: Clone,
+    {
+        let standardizer = Self::fit(data);
+        let data = data
+            .iter()
+            .map(|d| standardizer.transform_point(d))
+            .collect();
+        (standardizer, data)
+    }
+}
+
+/// Scales data so each dimension lies in the range 0..=1,
+/// i.e. min-max normalization.
+///
+/// Complex components are offset by the minimum real part and
+/// scaled by the range of the real parts in their dimension.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MinMaxScaler {
+    /// The minimum of each dimension
+    pub min: Vec<f64>,
+    /// The maximum of each dimension
+    pub max: Vec<f64>,
+}
+
+impl MinMaxScaler {
+    /// Calculates the minimum and maximum of each dimension of the given data.
+    #[must_use]
+    pub fn fit<T>(data: &[DataPoint<T>]) -> Self {
+        let dim = data.iter().map(|d| d.point.0.len()).max().unwrap_or(0);
+        let mut min = vec![f64::INFINITY; dim];
+        let mut max = vec![f64::NEG_INFINITY; dim];
+        for d in data {
+            for ((min, max), x) in min.iter_mut().zip(&mut max).zip(&d.point.0) {
+                *min = min.min(x.re);
+                *max = max.max(x.re);
+            }
+        }
+
+        Self { min, max }
+    }
+
+    /// Scales a point using the fitted minimum and maximum.
+    ///
+    /// Dimensions with zero range are only offset by the minimum,
+    /// and any dimensions that were not fitted are left unchanged.
+    #[must_use]
+    pub fn transform(&self, point: &Point) -> Point {
+        let mut point = point.clone();
+        for ((x, &min), &max) in point.0.iter_mut().zip(&self.min).zip(&self.max) {
+            *x -= Complex::from(min);
+            if max > min {
+                *x = x.scale((max - min).recip());
+            }
+        }
+        point
+    }
+
+    /// Reverses the scaling of a point, recovering the original point.
+    #[must_use]
+    pub fn inverse_transform(&self, point: &Point) -> Point {
+        let mut point = point.clone();
+        for ((x, &min), &max) in point.0.iter_mut().zip(&self.min).zip(&self.max) {
+            if max > min {
+                *x = x.scale(max - min);
+            }
+            *x += Complex::from(min);
+        }
+        point
+    }
+
+    /// Scales a data point using the fitted minimum and maximum.
+    #[inline]
+    #[must_use]
+    pub fn transform_point<T>(&self, data: &DataPoint<T>) -> DataPoint<T>
+    where
+        T: Clone,
+    {
+        DataPoint {
+            point: self.transform(&data.point),
+            class: data.class.clone(),
+            weight: data.weight,
+            id: data.id.clone(),
+        }
+    }
+
+    /// Fits a scaler to the given data, returning the
+    /// scaler and the scaled data.
+    #[must_use]
+    pub fn fit_transform<T>(data: &[DataPoint<T>]) -> (Self, Vec<DataPoint<T>>)
+    where
+        T: Clone,
+    {
+        let scaler = Self::fit(data);
+        let data = data.iter().map(|d| scaler.transform_point(d)).collect();
+        (scaler, data)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn standardizer() {
+        let data: Vec<_> = [(1.0, 100.0), (2.0, 300.0), (3.0, 200.0), (6.0, 400.0)]
+            .into_iter()
+            .map(|(x, y)| DataPoint {
+                point: Point(vec![x.into(), y.into()]),
+                class: (),
+                weight: 1.0,
+                id: None,
+            })
+            .collect();
+
+        let (_, data) = Standardizer::fit_transform(&data);
+        let fitted = Standardizer::fit(&data);
+        for (&mean, &std_dev) in fitted.mean.0.iter().zip(&fitted.std_dev) {
+            assert!(mean.re.abs() < 1e-12);
+            assert!(mean.im.abs() < 1e-12);
+            assert!((std_dev - 1.0).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn min_max_scaler() {
+        let data: Vec<_> = [(1.0, 5.0), (2.0, 5.0), (3.0, 5.0), (6.0, 5.0)]
+            .into_iter()
+            .map(|(x, y)| DataPoint {
+                point: Point(vec![x.into(), y.into()]),
+                class: (),
+                weight: 1.0,
+                id: None,
+            })
+            .collect();
+
+        let (scaler, scaled) = MinMaxScaler::fit_transform(&data);
+        for d in &scaled {
+            for x in &d.point.0 {
+                assert!((0.0..=1.0).contains(&x.re));
+            }
+        }
+        assert!((scaled[0].point.0[0].re).abs() < 1e-12);
+        assert!((scaled[3].point.0[0].re - 1.0).abs() < 1e-12);
+
+        // the constant dimension is only offset
+        assert!(scaled[0].point.0[1].re.abs() < 1e-12);
+
+        for (original, scaled) in data.iter().zip(&scaled) {
+            let recovered = scaler.inverse_transform(&scaled.point);
+            for (a, b) in original.point.0.iter().zip(&recovered.0) {
+                assert!((a.re - b.re).abs() < 1e-12);
+            }
+        }
+    }
+}
@@ -0,0 +1,266 @@
+//! This module provides interfaces to split data
+//! to validate classification algorithms.
+//!
+//! Author: Benjamin Hall
+
+use super::{accuracy, Classification};
+use crate::DataPoint;
+
+/// Randomly splits data into training data and test data, with
+/// the given fraction of the data being used as test data.
+///
+/// The same seed always produces the same split.
+///
+/// # Panics
+///
+/// Panics if the test fraction is not in the range 0.0..=1.0.
+#[must_use]
+pub fn train_test_split<T>(
+    data: &[DataPoint<T>],
+    test_fraction: f64,
+    seed: u64,
+) -> (Vec<DataPoint<T>>, Vec<DataPoint<T>>)
+where
+    T: Clone,
+{
+    assert!(
+        (0.0..=1.0).contains(&test_fraction),
+        "The test fraction must be between 0 and 1"
+    );
+
+    // shuffle a copy of the data
+    let mut train_data = data.to_vec();
+    fastrand::Rng::with_seed(seed).shuffle(&mut train_data);
+
+    // the end of the shuffled data is the test data
+    let test_len = (data.len() as f64 * test_fraction).round() as usize;
+    let test_data = train_data.split_off(data.len() - test_len);
+    (train_data, test_data)
+}
+
+/// Randomly splits data into training data and test data, with the
+/// given fraction of each classification being used as test data.
+///
+/// Unlike [`train_test_split`], each classification keeps its proportion
+/// of the data in both splits, so a rare classification cannot end up
+/// entirely in one split. The same seed always produces the same split.
+///
+/// # Panics
+///
+/// Panics if the test fraction is not in the range 0.0..=1.0.
+#[must_use]
+pub fn stratified_train_test_split<T>(
+    data: &[DataPoint<T>],
+    test_fraction: f64,
+    seed: u64,
+) -> (Vec<DataPoint<T>>, Vec<DataPoint<T>>)
+where
+    T: Clone + PartialEq,
+{
+    assert!(
+        (0.0..=1.0).contains(&test_fraction),
+        "The test fraction must be between 0 and 1"
+    );
+
+    // group data by classification, in the order the classifications first appear
+    let mut groups: Vec<Vec<DataPoint<T>>> = Vec::new();
+    for d in data {
+        match groups.iter_mut().find(|g| g[0].class == d.class) {
+            Some(group) => group.push(d.clone()),
+            None => groups.push(vec![d.clone()]),
+        }
+    }
+
+    let mut rng = fastrand::Rng::with_seed(seed);
+    let mut train_data = Vec::with_capacity(data.len());
+    let mut test_data = Vec::new();
+    for mut group in groups {
+        // shuffle each classification, the end of which is the test data
+        rng.shuffle(&mut group);
+        let test_len = (group.len() as f64 * test_fraction).round() as usize;
+        test_data.extend(group.split_off(group.len() - test_len));
+        train_data.extend(group);
+    }
+    (train_data, test_data)
+}
+
+/// Runs k-fold cross-validation of a classification algorithm on the
+/// given data, returning the mean accuracy across the folds.
+///
+/// Every `k`-th data point is placed in the same fold, so data sorted
+/// by classification is spread evenly across the folds. Each fold is
+/// classified using the rest of the data as training data.
+///
+/// # Panics
+///
+/// Panics if `k` is less than 2 or greater than the number of data points.
+#[must_use]
+pub fn cross_validate<T, F>(data: &[DataPoint<T>], k: usize, classify: F) -> f64
+where
+    T: Clone + PartialEq,
+    F: for<'a> Fn(&[DataPoint<T>], &'a [DataPoint<T>]) -> Vec<Classification<'a, T>>,
+{
+    assert!(
+        (2..=data.len()).contains(&k),
+        "The number of folds must be between 2 and the number of data points"
+    );
+
+    let total: f64 = (0..k)
+        .map(|fold| {
+            // split the data into this fold and the rest
+            let (test_data, train_data): (Vec<_>, Vec<_>) =
+                data.iter().enumerate().partition(|(i, _)| i % k == fold);
+            let test_data: Vec<_> = test_data.into_iter().map(|(_, d)| d.clone()).collect();
+            let train_data: Vec<_> = train_data.into_iter().map(|(_, d)| d.clone()).collect();
+
+            accuracy(&classify(&train_data, &test_data))
+        })
+        .sum();
+    total / k as f64
+}
+
+/// Runs leave-one-out cross-validation of a classification algorithm
+/// on the given data, returning the overall accuracy.
+///
+/// Each data point is classified using the rest of the data as
+/// training data. This is k-fold cross-validation with one fold
+/// per data point, which suits small data sets. Returns 0 if
+/// there is no data. A data point is only counted as correct if the
+/// algorithm returns a classification for it.
+#[must_use]
+pub fn leave_one_out<T, F>(data: &[DataPoint<T>], classify: F) -> f64
+where
+    T: Clone + PartialEq,
+    F: for<'a> Fn(&[DataPoint<T>], &'a [DataPoint<T>]) -> Vec<Classification<'a, T>>,
+{
+    if data.is_empty() {
+        return 0.0;
+    }
+
+    let correct = (0..data.len())
+        .filter(|&i| {
+            // train on every data point except this one
+            let mut train_data = data.to_vec();
+            let test_data = [train_data.remove(i)];
+
+            classify(&train_data, &test_data)
+                .first()
+                .is_some_and(|c| c.class_guess == c.data.class)
+        })
+        .count();
+    correct as f64 / data.len() as f64
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{classify::k_nearest_neighbor, Point};
+    use std::cell::Cell;
+
+    #[test]
+    fn split_sizes() {
+        let data: Vec<_> = (0..10)
+            .map(|i| DataPoint {
+                point: Point(vec![f64::from(i).into()]),
+                class: i,
+                weight: 1.0,
+                id: None,
+            })
+            .collect();
+
+        let (train_data, test_data) = train_test_split(&data, 0.3, 0);
+        assert_eq!(train_data.len(), 7);
+        assert_eq!(test_data.len(), 3);
+
+        // every data point is in exactly one of the splits
+        let mut classes: Vec<_> = train_data
+            .iter()
+            .chain(&test_data)
+            .map(|d| d.class)
+            .collect();
+        classes.sort_unstable();
+        assert_eq!(classes, (0..10).collect::<Vec<_>>());
+
+        // the same seed produces the same split
+        assert_eq!(train_test_split(&data, 0.3, 0), (train_data, test_data));
+    }
+
+    #[test]
+    fn cross_validation() {
+        // two well-separated classifications
+        let data: Vec<_> = (0..20)
+            .map(|i| {
+                let (offset, class) = if i < 10 {
+                    (0.0, "red")
+                } else {
+                    (100.0, "blue")
+                };
+                DataPoint {
+                    point: Point(vec![(offset + f64::from(i)).into()]),
+                    class,
+                    weight: 1.0,
+                    id: None,
+                }
+            })
+            .collect();
+
+        let accuracy = cross_validate(&data, 5, |train_data, test_data| {
+            k_nearest_neighbor(train_data, test_data, 3)
+        });
+        assert!((accuracy - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn leave_one_out_folds() {
+        let data: Vec<_> = [
+            (0.0, "red"),
+            (1.0, "red"),
+            (2.0, "red"),
+            (10.0, "blue"),
+            (11.0, "blue"),
+        ]
+        .into_iter()
+        .map(|(x, class)| DataPoint {
+            point: Point(vec![x.into()]),
+            class,
+            weight: 1.0,
+            id: None,
+        })
+        .collect();
+
+        let folds = Cell::new(0);
+        let accuracy = leave_one_out(&data, |train_data, test_data| {
+            folds.set(folds.get() + 1);
+            assert_eq!(train_data.len(), data.len() - 1);
+            assert_eq!(test_data.len(), 1);
+            k_nearest_neighbor(train_data, test_data, 1)
+        });
+        assert_eq!(folds.get(), data.len());
+        assert!((accuracy - 1.0).abs() < 1e-12);
+
+        // no classifications are not counted as correct
+        let accuracy = leave_one_out(&data, |_, _| Vec::new());
+        assert!(accuracy.abs() < 1e-12);
+    }
+
+    #[test]
+    fn stratified_split() {
+        // three red data points for every blue data point
+        let data: Vec<_> = (0..16)
+            .map(|i| DataPoint {
+                point: Point(vec![f64::from(i).into()]),
+                class: if i % 4 == 0 { "blue" } else { "red" },
+                weight: 1.0,
+                id: None,
+            })
+            .collect();
+
+        let (train_data, test_data) = stratified_train_test_split(&data, 0.25, 0);
+        let count =
+            |data: &[DataPoint<&str>], class| data.iter().filter(|d| d.class == class).count();
+        assert_eq!(count(&train_data, "red"), 9);
+        assert_eq!(count(&train_data, "blue"), 3);
+        assert_eq!(count(&test_data, "red"), 3);
+        assert_eq!(count(&test_data, "blue"), 1);
+    }
+}
@@ -3,6 +3,9 @@
 //!
 //! Author: Benjamin Hall
 
+use crate::{classify::kmeans, Point};
+use std::{error::Error, fmt::Display, str::FromStr};
+
 /// Stores an RGB color.
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub struct Rgb {
@@ -11,6 +14,383 @@ pub struct Rgb {
     pub b: u8,
 }
 
+/// The CSS basic color keywords.
+const NAMED_COLORS: [(&str, Rgb); 16] = [
+    ("black", Rgb { r: 0, g: 0, b: 0 }),
+    (
+        "silver",
+        Rgb {
+            r: 192,
+            g: 192,
+            b: 192,
+        },
+    ),
+    (
+        "gray",
+        Rgb {
+            r: 128,
+            g: 128,
+            b: 128,
+        },
+    ),
+    (
+        "white",
+        Rgb {
+            r: 255,
+            g: 255,
+            b: 255,
+        },
+    ),
+    ("maroon", Rgb { r: 128, g: 0, b: 0 }),
+    ("red", Rgb { r: 255, g: 0, b: 0 }),
+    (
+        "purple",
+        Rgb {
+            r: 128,
+            g: 0,
+            b: 128,
+        },
+    ),
+    (
+        "fuchsia",
+        Rgb {
+            r: 255,
+            g: 0,
+            b: 255,
+        },
+    ),
+    ("green", Rgb { r: 0, g: 128, b: 0 }),
+    ("lime", Rgb { r: 0, g: 255, b: 0 }),
+    (
+        "olive",
+        Rgb {
+            r: 128,
+            g: 128,
+            b: 0,
+        },
+    ),
+    (
+        "yellow",
+        Rgb {
+            r: 255,
+            g: 255,
+            b: 0,
+        },
+    ),
+    ("navy", Rgb { r: 0, g: 0, b: 128 }),
+    ("blue", Rgb { r: 0, g: 0, b: 255 }),
+    (
+        "teal",
+        Rgb {
+            r: 0,
+            g: 128,
+            b: 128,
+        },
+    ),
+    (
+        "aqua",
+        Rgb {
+            r: 0,
+            g: 255,
+            b: 255,
+        },
+    ),
+];
+
+impl Rgb {
+    /// Looks up a color by its CSS basic color keyword, ignoring case.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    /// ```
+    /// # use data_utils::color::Rgb;
+    /// assert_eq!(Rgb::from_name("Teal"), Some(Rgb { r: 0, g: 128, b: 128 }));
+    /// assert_eq!(Rgb::from_name("not a color"), None);
+    /// ```
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        NAMED_COLORS
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name.trim()))
+            .map(|&(_, rgb)| rgb)
+    }
+
+    /// Finds the CSS basic color keyword of the named color
+    /// perceptually closest to this color, by CIELAB distance.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    /// ```
+    /// # use data_utils::color::Rgb;
+    /// assert_eq!(Rgb { r: 10, g: 120, b: 130 }.nearest_name(), "teal");
+    /// ```
+    #[must_use]
+    pub fn nearest_name(&self) -> &'static str {
+        let lab = Lab::from(*self);
+        NAMED_COLORS
+            .iter()
+            .map(|&(name, rgb)| (name, lab.delta_e(&Lab::from(rgb))))
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map_or("", |(name, _)| name)
+    }
+
+    /// Formats the color as a hex string, `#rrggbb`.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    /// ```
+    /// # use data_utils::color::Rgb;
+    /// let rgb = Rgb { r: 255, g: 136, b: 0 };
+    /// assert_eq!(rgb.to_hex(), "#ff8800");
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn to_hex(&self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    }
+
+    /// Linearly interpolates between this color and another color,
+    /// where a fraction of 0 is this color and 1 is the other color.
+    ///
+    /// The channels are interpolated as stored, i.e. in gamma-encoded sRGB
+    /// space rather than linear light, so the midpoint of black and white
+    /// is a channel value of 128 rather than a perceptually darker gray.
+    /// Fractions outside of 0..=1 extrapolate, with the channels clamped.
+    #[must_use]
+    pub fn blend(self, other: Self, t: f64) -> Self {
+        let channel = |a: u8, b: u8| {
+            let (a, b) = (a as f64, b as f64);
+            (a + (b - a) * t).round().clamp(0.0, 255.0) as u8
+        };
+        Self {
+            r: channel(self.r, other.r),
+            g: channel(self.g, other.g),
+            b: channel(self.b, other.b),
+        }
+    }
+
+    /// Converts the color to linear light, undoing the sRGB gamma
+    /// encoding so each channel is proportional to its intensity.
+    ///
+    /// Each channel is from 0 to 1. Colors should be averaged or
+    /// blended in linear light to be physically accurate.
+    #[inline]
+    #[must_use]
+    pub fn to_linear(&self) -> [f64; 3] {
+        [self.r, self.g, self.b].map(|c| to_linear_channel(c as f64 / 255.0))
+    }
+
+    /// Converts a color in linear light to sRGB, applying the sRGB
+    /// gamma encoding. Channels outside of 0..=1 are clamped.
+    #[inline]
+    #[must_use]
+    pub fn from_linear(rgb: [f64; 3]) -> Self {
+        let [r, g, b] =
+            rgb.map(|c| (from_linear_channel(c) * 255.0).round().clamp(0.0, 255.0) as u8);
+        Self { r, g, b }
+    }
+
+    /// Inverts each channel of the color.
+    #[inline]
+    #[must_use]
+    pub const fn invert(self) -> Self {
+        Self {
+            r: u8::MAX - self.r,
+            g: u8::MAX - self.g,
+            b: u8::MAX - self.b,
+        }
+    }
+
+    /// Finds the complementary color, which has the same saturation
+    /// and value but with the hue rotated by 180 degrees.
+    #[inline]
+    #[must_use]
+    pub fn complement(self) -> Self {
+        let mut hsv = Hsv::from(self);
+        hsv.h = (hsv.h + 180.0).rem_euclid(360.0);
+        Self::from(hsv)
+    }
+
+    /// Packs the color into the low 24 bits of an integer, `0x00RRGGBB`.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    /// ```
+    /// # use data_utils::color::Rgb;
+    /// let rgb = Rgb { r: 0x12, g: 0x34, b: 0x56 };
+    /// assert_eq!(rgb.to_u32(), 0x0012_3456);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn to_u32(&self) -> u32 {
+        u32::from_be_bytes([0, self.r, self.g, self.b])
+    }
+
+    /// Calculates the luminance of the color from 0 to 1,
+    /// using the Rec. 709 weights of each channel.
+    #[inline]
+    #[must_use]
+    pub fn luminance(&self) -> f64 {
+        let (r, g, b) = (self.r as f64, self.g as f64, self.b as f64);
+        (0.2126 * r + 0.7152 * g + 0.0722 * b) / 255.0
+    }
+
+    /// Calculates the relative luminance of the color from 0 to 1, as
+    /// defined by WCAG, using the Rec. 709 weights in linear light.
+    #[inline]
+    #[must_use]
+    pub fn relative_luminance(&self) -> f64 {
+        let [r, g, b] = self.to_linear();
+        0.2126 * r + 0.7152 * g + 0.0722 * b
+    }
+
+    /// Converts the color to the gray with the same luminance.
+    #[inline]
+    #[must_use]
+    pub fn to_grayscale(&self) -> Self {
+        let gray = (self.luminance() * 255.0).round() as u8;
+        Self {
+            r: gray,
+            g: gray,
+            b: gray,
+        }
+    }
+
+    /// Applies a sepia tone to the color, using the standard sepia
+    /// matrix and clamping each channel.
+    #[must_use]
+    pub fn sepia(self) -> Self {
+        let (r, g, b) = (self.r as f64, self.g as f64, self.b as f64);
+        let channel = |x: f64| x.round().clamp(0.0, 255.0) as u8;
+        Self {
+            r: channel(0.393 * r + 0.769 * g + 0.189 * b),
+            g: channel(0.349 * r + 0.686 * g + 0.168 * b),
+            b: channel(0.272 * r + 0.534 * g + 0.131 * b),
+        }
+    }
+}
+
+/// Calculates the WCAG contrast ratio between two colors, from 1 for
+/// identical luminance to 21 for black and white.
+///
+/// WCAG requires a ratio of at least 4.5 for normal text to be legible.
+///
+/// # Examples
+///
+/// Basic usage:
+/// ```
+/// # use data_utils::color::{contrast_ratio, Rgb};
+/// let black = Rgb { r: 0, g: 0, b: 0 };
+/// let white = Rgb { r: 255, g: 255, b: 255 };
+/// assert!((contrast_ratio(black, white) - 21.0).abs() < 1e-9);
+/// ```
+#[must_use]
+pub fn contrast_ratio(a: Rgb, b: Rgb) -> f64 {
+    let (a, b) = (a.relative_luminance(), b.relative_luminance());
+    (a.max(b) + 0.05) / (a.min(b) + 0.05)
+}
+
+/// Reduces a list of colors to a palette of representative colors,
+/// by clustering the colors in RGB space with k-means.
+///
+/// The same seed always produces the same palette.
+///
+/// # Panics
+///
+/// Panics if the palette size is 0 or greater than the number of colors.
+#[must_use]
+pub fn quantize(colors: &[Rgb], palette_size: usize, seed: u64) -> Vec<Rgb> {
+    let points: Vec<_> = colors
+        .iter()
+        .map(|c| {
+            Point(vec![
+                (c.r as f64).into(),
+                (c.g as f64).into(),
+                (c.b as f64).into(),
+            ])
+        })
+        .collect();
+
+    let (centroids, _) = kmeans(&points, palette_size, 100, seed);
+    centroids
+        .iter()
+        .map(|centroid| {
+            let channel = |i: usize| centroid.0[i].re.round().clamp(0.0, 255.0) as u8;
+            Rgb {
+                r: channel(0),
+                g: channel(1),
+                b: channel(2),
+            }
+        })
+        .collect()
+}
+
+/// Stores an RGB color with an alpha channel,
+/// where an alpha of 0 is fully transparent.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Rgba {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: u8,
+}
+
+impl Rgba {
+    /// Composites this color over the given background color,
+    /// using source-over alpha compositing.
+    #[must_use]
+    pub fn over(self, background: Self) -> Self {
+        let alpha_src = self.a as f64 / 255.0;
+        let alpha_bg = background.a as f64 / 255.0;
+        // α = αₛ + α_b(1 - αₛ)
+        let alpha = alpha_src + alpha_bg * (1.0 - alpha_src);
+        if alpha == 0.0 {
+            return Self::default();
+        }
+
+        // C = (Cₛαₛ + C_bα_b(1 - αₛ)) / α
+        let channel = |src: u8, bg: u8| {
+            let c = (src as f64 * alpha_src + bg as f64 * alpha_bg * (1.0 - alpha_src)) / alpha;
+            c.round().clamp(0.0, 255.0) as u8
+        };
+        Self {
+            r: channel(self.r, background.r),
+            g: channel(self.g, background.g),
+            b: channel(self.b, background.b),
+            a: (alpha * 255.0).round() as u8,
+        }
+    }
+}
+
+impl From<Rgb> for Rgba {
+    /// Converts an RGB color to a fully opaque RGBA color.
+    #[inline]
+    fn from(value: Rgb) -> Self {
+        Self {
+            r: value.r,
+            g: value.g,
+            b: value.b,
+            a: u8::MAX,
+        }
+    }
+}
+
+impl From<Rgba> for Rgb {
+    /// Converts an RGBA color to an RGB color, dropping the alpha channel.
+    #[inline]
+    fn from(value: Rgba) -> Self {
+        Self {
+            r: value.r,
+            g: value.g,
+            b: value.b,
+        }
+    }
+}
+
 /// Stores an HSV color.
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
 pub struct Hsv {
@@ -19,10 +399,280 @@ pub struct Hsv {
     pub v: f64,
 }
 
+impl Display for Hsv {
+    /// Formats the color as `hsv(h, s, v)`.
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "hsv({}, {}, {})", self.h, self.s, self.v)
+    }
+}
+
+impl FromStr for Hsv {
+    type Err = ParseColorError;
+
+    /// Parses a color formatted as `hsv(h, s, v)`.
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let components = input
+            .trim()
+            .strip_prefix("hsv(")
+            .and_then(|rest| rest.strip_suffix(')'))
+            .ok_or_else(|| ParseColorError::new(input))?;
+
+        let components: Vec<_> = components
+            .split(',')
+            .map(|c| c.trim().parse::<f64>())
+            .collect::<Result<_, _>>()
+            .map_err(|_| ParseColorError::new(input))?;
+        let [h, s, v] = components[..] else {
+            return Err(ParseColorError::new(input));
+        };
+        Ok(Self { h, s, v })
+    }
+}
+
+/// Stores an HSL color.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Hsl {
+    pub h: f64,
+    pub s: f64,
+    pub l: f64,
+}
+
+/// Stores a CMYK color.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Cmyk {
+    pub c: f64,
+    pub m: f64,
+    pub y: f64,
+    pub k: f64,
+}
+
+/// Stores a YCbCr color, with each channel from 0 to 255,
+/// where `cb` and `cr` are 128 for grays.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct YCbCr {
+    pub y: f64,
+    pub cb: f64,
+    pub cr: f64,
+}
+
+/// Stores a CIE XYZ color, using the sRGB primaries and D65 white point,
+/// where the white point has a luminance `y` of 1.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Xyz {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+/// Stores a CIELAB color, relative to the D65 white point.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Lab {
+    pub l: f64,
+    pub a: f64,
+    pub b: f64,
+}
+
+impl Lab {
+    /// Calculates the CIE76 color difference between two colors,
+    /// the Euclidean distance between them in CIELAB space.
+    ///
+    /// A difference of about 2.3 is just noticeable.
+    #[inline]
+    #[must_use]
+    pub fn delta_e(&self, other: &Self) -> f64 {
+        let (dl, da, db) = (self.l - other.l, self.a - other.a, self.b - other.b);
+        (dl * dl + da * da + db * db).sqrt()
+    }
+
+    /// Calculates the CIEDE2000 color difference between two colors.
+    ///
+    /// This corrects CIE76 for the non-uniformities of CIELAB,
+    /// particularly in saturated blues and near-neutral colors.
+    #[must_use]
+    pub fn delta_e_2000(&self, other: &Self) -> f64 {
+        /// 25⁷, used to weight chroma against a reference
+        const POW25_7: f64 = 6_103_515_625.0;
+
+        // adjust a* so that neutral colors have a hue
+        let c_bar = (self.a.hypot(self.b) + other.a.hypot(other.b)) / 2.0;
+        let g = 0.5 * (1.0 - (c_bar.powi(7) / (c_bar.powi(7) + POW25_7)).sqrt());
+        let prime = |lab: &Self| {
+            let a = (1.0 + g) * lab.a;
+            let c = a.hypot(lab.b);
+            let h = if c == 0.0 {
+                0.0
+            } else {
+                lab.b.atan2(a).to_degrees().rem_euclid(360.0)
+            };
+            (c, h)
+        };
+        let (c1, h1) = prime(self);
+        let (c2, h2) = prime(other);
+
+        // differences in lightness, chroma, and hue
+        let dl = other.l - self.l;
+        let dc = c2 - c1;
+        let dh = if c1 * c2 == 0.0 {
+            0.0
+        } else if (h2 - h1).abs() <= 180.0 {
+            h2 - h1
+        } else if h2 > h1 {
+            h2 - h1 - 360.0
+        } else {
+            h2 - h1 + 360.0
+        };
+        let dh = 2.0 * (c1 * c2).sqrt() * (dh / 2.0).to_radians().sin();
+
+        // means of lightness, chroma, and hue
+        let l_bar = (self.l + other.l) / 2.0;
+        let c_bar = (c1 + c2) / 2.0;
+        let h_bar = if c1 * c2 == 0.0 {
+            h1 + h2
+        } else if (h1 - h2).abs() <= 180.0 {
+            (h1 + h2) / 2.0
+        } else if h1 + h2 < 360.0 {
+            (h1 + h2 + 360.0) / 2.0
+        } else {
+            (h1 + h2 - 360.0) / 2.0
+        };
+
+        // weighting functions
+        let cos_deg = |x: f64| x.to_radians().cos();
+        let t = 1.0 - 0.17 * cos_deg(h_bar - 30.0)
+            + 0.24 * cos_deg(2.0 * h_bar)
+            + 0.32 * cos_deg(3.0 * h_bar + 6.0)
+            - 0.20 * cos_deg(4.0 * h_bar - 63.0);
+        let l_offset = (l_bar - 50.0).powi(2);
+        let s_l = 1.0 + 0.015 * l_offset / (20.0 + l_offset).sqrt();
+        let s_c = 1.0 + 0.045 * c_bar;
+        let s_h = 1.0 + 0.015 * c_bar * t;
+
+        // rotation term for blue hues
+        let d_theta = 30.0 * (-((h_bar - 275.0) / 25.0).powi(2)).exp();
+        let r_c = 2.0 * (c_bar.powi(7) / (c_bar.powi(7) + POW25_7)).sqrt();
+        let r_t = -(2.0 * d_theta).to_radians().sin() * r_c;
+
+        let (dl, dc, dh) = (dl / s_l, dc / s_c, dh / s_h);
+        (dl * dl + dc * dc + dh * dh + r_t * dc * dh).sqrt()
+    }
+}
+
 /// An enum of possible colors.
 pub enum Color {
     Rgb(Rgb),
     Hsv(Hsv),
+    Hsl(Hsl),
+    Cmyk(Cmyk),
+}
+
+/// An error returned when a color cannot be parsed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseColorError {
+    /// The string that could not be parsed
+    input: String,
+}
+
+impl ParseColorError {
+    /// Creates an error for the given string.
+    #[inline]
+    fn new(input: &str) -> Self {
+        Self {
+            input: input.to_owned(),
+        }
+    }
+}
+
+impl Display for ParseColorError {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid color: {}", self.input)
+    }
+}
+
+impl Error for ParseColorError {}
+
+/// Calculates the hue of an RGB color, in degrees, from its
+/// normalized channels, maximum channel, and chroma.
+#[inline]
+fn hue(r: f64, g: f64, b: f64, c_max: f64, chroma: f64) -> f64 {
+    if chroma == 0.0 {
+        0.0
+    } else if c_max == r {
+        60.0 * ((g - b) / chroma).rem_euclid(6.0)
+    } else if c_max == g {
+        60.0 * ((b - r) / chroma + 2.0)
+    } else {
+        60.0 * ((r - g) / chroma + 4.0)
+    }
+}
+
+/// Converts a hue, chroma, and offset added to each channel to an RGB color.
+///
+/// Hues outside of 0..360 degrees are wrapped into the range.
+#[inline]
+fn rgb_from_hue(h: f64, c: f64, m: f64) -> Rgb {
+    let h = h.rem_euclid(360.0);
+    let x = c * (1.0 - ((h / 60.0) % 2.0 - 1.0).abs());
+
+    let (r, g, b) = if (0.0..60.0).contains(&h) {
+        (c, x, 0.0)
+    } else if (60.0..120.0).contains(&h) {
+        (x, c, 0.0)
+    } else if (120.0..180.0).contains(&h) {
+        (0.0, c, x)
+    } else if (180.0..240.0).contains(&h) {
+        (0.0, x, c)
+    } else if (240.0..300.0).contains(&h) {
+        (x, 0.0, c)
+    } else {
+        (c, 0.0, x)
+    };
+    // round to the nearest channel value so conversions round-trip
+    let channel = |x: f64| ((x + m) * 255.0).round().clamp(0.0, 255.0) as u8;
+    Rgb {
+        r: channel(r),
+        g: channel(g),
+        b: channel(b),
+    }
+}
+
+impl From<u32> for Rgb {
+    /// Unpacks a color from the low 24 bits of an integer, `0x00RRGGBB`.
+    /// The high 8 bits are ignored.
+    #[inline]
+    fn from(value: u32) -> Self {
+        let [_, r, g, b] = value.to_be_bytes();
+        Self { r, g, b }
+    }
+}
+
+impl FromStr for Rgb {
+    type Err = ParseColorError;
+
+    /// Parses a hex color, `#rrggbb` or `#rgb`, where the `#` is optional.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let hex = s.strip_prefix('#').unwrap_or(s);
+        if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(ParseColorError::new(s));
+        }
+
+        let channel = |digits: &str| u8::from_str_radix(digits, 16).unwrap_or_default();
+        match hex.len() {
+            6 => Ok(Self {
+                r: channel(&hex[0..2]),
+                g: channel(&hex[2..4]),
+                b: channel(&hex[4..6]),
+            }),
+            // each digit is repeated, e.g. #f80 is #ff8800
+            3 => Ok(Self {
+                r: channel(&hex[0..1]) * 17,
+                g: channel(&hex[1..2]) * 17,
+                b: channel(&hex[2..3]) * 17,
+            }),
+            _ => Err(ParseColorError::new(s)),
+        }
+    }
 }
 
 impl From<Rgb> for Hsv {
@@ -36,15 +686,7 @@ impl From<Rgb> for Hsv {
         let chroma = c_max - c_min;
 
         let v = c_max;
-        let h = if chroma == 0.0 {
-            0.0
-        } else if v == r {
-            60.0 * ((g - b) / chroma).rem_euclid(6.0)
-        } else if v == g {
-            60.0 * ((b - r) / chroma + 2.0)
-        } else {
-            60.0 * ((r - g) / chroma + 4.0)
-        };
+        let h = hue(r, g, b, c_max, chroma);
         let s = if v == 0.0 { 0.0 } else { chroma / v };
 
         Self { h, s, v }
@@ -53,26 +695,245 @@ impl From<Rgb> for Hsv {
 
 impl From<Hsv> for Rgb {
     fn from(value: Hsv) -> Self {
-        let c = value.v * value.s;
-        let x = c * (1.0 - ((value.h / 60.0) % 2.0 - 1.0).abs());
-        let m = value.v - c;
-
-        let (r, g, b) = if value.h >= 0.0 && value.h < 60.0 {
-            (c, x, 0.0)
-        } else if value.h >= 60.0 && value.h < 120.0 {
-            (x, c, 0.0)
-        } else if value.h >= 120.0 && value.h < 180.0 {
-            (0.0, c, x)
-        } else if value.h >= 180.0 && value.h < 240.0 {
-            (0.0, x, c)
-        } else if value.h >= 240.0 && value.h < 300.0 {
-            (x, 0.0, c)
+        // saturation and value outside of 0..=1 are clamped
+        let s = value.s.clamp(0.0, 1.0);
+        let v = value.v.clamp(0.0, 1.0);
+
+        let c = v * s;
+        let m = v - c;
+        rgb_from_hue(value.h, c, m)
+    }
+}
+
+impl From<Rgb> for Hsl {
+    fn from(value: Rgb) -> Self {
+        let r = (value.r as f64) / 255.0;
+        let g = (value.g as f64) / 255.0;
+        let b = (value.b as f64) / 255.0;
+
+        let c_max = r.max(g).max(b);
+        let c_min = r.min(g).min(b);
+        let chroma = c_max - c_min;
+
+        let l = (c_max + c_min) / 2.0;
+        let h = hue(r, g, b, c_max, chroma);
+        let s = if l == 0.0 || l == 1.0 {
+            0.0
         } else {
-            (c, 0.0, x)
+            chroma / (1.0 - (2.0 * l - 1.0).abs())
         };
-        let (r, g, b) = ((r + m) * 255.0, (g + m) * 255.0, (b + m) * 255.0);
-        let (r, g, b) = (r as u8, g as u8, b as u8);
-        Self { r, g, b }
+
+        Self { h, s, l }
+    }
+}
+
+impl From<Hsl> for Rgb {
+    fn from(value: Hsl) -> Self {
+        // saturation and lightness outside of 0..=1 are clamped
+        let s = value.s.clamp(0.0, 1.0);
+        let l = value.l.clamp(0.0, 1.0);
+
+        let c = (1.0 - (2.0 * l - 1.0).abs()) * s;
+        let m = l - c / 2.0;
+        rgb_from_hue(value.h, c, m)
+    }
+}
+
+impl From<Rgb> for Cmyk {
+    fn from(value: Rgb) -> Self {
+        let r = (value.r as f64) / 255.0;
+        let g = (value.g as f64) / 255.0;
+        let b = (value.b as f64) / 255.0;
+
+        let k = 1.0 - r.max(g).max(b);
+        if k == 1.0 {
+            // pure black, the other channels are undefined
+            return Self {
+                c: 0.0,
+                m: 0.0,
+                y: 0.0,
+                k,
+            };
+        }
+
+        let c = (1.0 - r - k) / (1.0 - k);
+        let m = (1.0 - g - k) / (1.0 - k);
+        let y = (1.0 - b - k) / (1.0 - k);
+        Self { c, m, y, k }
+    }
+}
+
+impl From<Cmyk> for Rgb {
+    fn from(value: Cmyk) -> Self {
+        let channel = |x: f64| (255.0 * (1.0 - x) * (1.0 - value.k)).round() as u8;
+        Self {
+            r: channel(value.c),
+            g: channel(value.m),
+            b: channel(value.y),
+        }
+    }
+}
+
+impl From<Rgb> for YCbCr {
+    /// Converts an RGB color to full-range YCbCr, using the ITU-R BT.601 coefficients.
+    fn from(value: Rgb) -> Self {
+        let (r, g, b) = (value.r as f64, value.g as f64, value.b as f64);
+
+        let y = 0.299 * r + 0.587 * g + 0.114 * b;
+        let cb = 128.0 - 0.168_736 * r - 0.331_264 * g + 0.5 * b;
+        let cr = 128.0 + 0.5 * r - 0.418_688 * g - 0.081_312 * b;
+        Self { y, cb, cr }
+    }
+}
+
+impl From<YCbCr> for Rgb {
+    /// Converts a full-range YCbCr color to RGB, using the ITU-R BT.601
+    /// coefficients and clamping colors outside of the RGB gamut.
+    fn from(value: YCbCr) -> Self {
+        let (y, cb, cr) = (value.y, value.cb - 128.0, value.cr - 128.0);
+
+        let channel = |x: f64| x.round().clamp(0.0, 255.0) as u8;
+        Self {
+            r: channel(y + 1.402 * cr),
+            g: channel(y - 0.344_136 * cb - 0.714_136 * cr),
+            b: channel(y + 1.772 * cb),
+        }
+    }
+}
+
+/// The CIE XYZ coordinates of the D65 white point.
+const D65_WHITE: Xyz = Xyz {
+    x: 0.95047,
+    y: 1.0,
+    z: 1.08883,
+};
+
+/// Converts a gamma-encoded sRGB channel from 0 to 1 to linear light.
+#[inline]
+fn to_linear_channel(c: f64) -> f64 {
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// Converts a linear light channel from 0 to 1 to gamma-encoded sRGB.
+#[inline]
+fn from_linear_channel(c: f64) -> f64 {
+    if c <= 0.003_130_8 {
+        c * 12.92
+    } else {
+        1.055 * c.powf(2.4f64.recip()) - 0.055
+    }
+}
+
+impl From<Rgb> for Xyz {
+    fn from(value: Rgb) -> Self {
+        let [r, g, b] = value.to_linear();
+
+        let x = 0.412_456_4 * r + 0.357_576_1 * g + 0.180_437_5 * b;
+        let y = 0.212_672_9 * r + 0.715_152_2 * g + 0.072_175_0 * b;
+        let z = 0.019_333_9 * r + 0.119_192_0 * g + 0.950_304_1 * b;
+        Self { x, y, z }
+    }
+}
+
+impl From<Xyz> for Rgb {
+    /// Converts an XYZ color to RGB, clamping colors outside of the sRGB gamut.
+    fn from(value: Xyz) -> Self {
+        let Xyz { x, y, z } = value;
+        let r = 3.240_454_2 * x - 1.537_138_5 * y - 0.498_531_4 * z;
+        let g = -0.969_266_0 * x + 1.876_010_8 * y + 0.041_556_0 * z;
+        let b = 0.055_643_4 * x - 0.204_025_9 * y + 1.057_225_2 * z;
+        Self::from_linear([r, g, b])
+    }
+}
+
+/// The threshold between the linear and cube root parts of the CIELAB function.
+const LAB_DELTA: f64 = 6.0 / 29.0;
+
+impl From<Xyz> for Lab {
+    fn from(value: Xyz) -> Self {
+        let f = |t: f64| {
+            if t > LAB_DELTA.powi(3) {
+                t.cbrt()
+            } else {
+                t / (3.0 * LAB_DELTA * LAB_DELTA) + 4.0 / 29.0
+            }
+        };
+        let fx = f(value.x / D65_WHITE.x);
+        let fy = f(value.y / D65_WHITE.y);
+        let fz = f(value.z / D65_WHITE.z);
+
+        Self {
+            l: 116.0 * fy - 16.0,
+            a: 500.0 * (fx - fy),
+            b: 200.0 * (fy - fz),
+        }
+    }
+}
+
+impl From<Lab> for Xyz {
+    fn from(value: Lab) -> Self {
+        let fy = (value.l + 16.0) / 116.0;
+        let fx = fy + value.a / 500.0;
+        let fz = fy - value.b / 200.0;
+
+        let f_inv = |t: f64| {
+            if t > LAB_DELTA {
+                t.powi(3)
+            } else {
+                3.0 * LAB_DELTA * LAB_DELTA * (t - 4.0 / 29.0)
+            }
+        };
+        Self {
+            x: D65_WHITE.x * f_inv(fx),
+            y: D65_WHITE.y * f_inv(fy),
+            z: D65_WHITE.z * f_inv(fz),
+        }
+    }
+}
+
+impl From<Rgb> for Lab {
+    #[inline]
+    fn from(value: Rgb) -> Self {
+        Self::from(Xyz::from(value))
+    }
+}
+
+impl From<Lab> for Rgb {
+    #[inline]
+    fn from(value: Lab) -> Self {
+        Self::from(Xyz::from(value))
+    }
+}
+
+impl From<Hsl> for Hsv {
+    fn from(value: Hsl) -> Self {
+        // v = l + s_l min(l, 1 - l)
+        let v = value.l + value.s * value.l.min(1.0 - value.l);
+        let s = if v == 0.0 {
+            0.0
+        } else {
+            2.0 * (1.0 - value.l / v)
+        };
+
+        Self { h: value.h, s, v }
+    }
+}
+
+impl From<Hsv> for Hsl {
+    fn from(value: Hsv) -> Self {
+        // l = v (1 - s_v / 2)
+        let l = value.v * (1.0 - value.s / 2.0);
+        let s = if l == 0.0 || l == 1.0 {
+            0.0
+        } else {
+            (value.v - l) / l.min(1.0 - l)
+        };
+
+        Self { h: value.h, s, l }
     }
 }
 
@@ -81,6 +942,19 @@ impl From<Color> for Hsv {
         match value {
             Color::Rgb(rgb) => Self::from(rgb),
             Color::Hsv(hsv) => hsv,
+            Color::Hsl(hsl) => Self::from(hsl),
+            Color::Cmyk(cmyk) => Self::from(Rgb::from(cmyk)),
+        }
+    }
+}
+
+impl From<Color> for Hsl {
+    fn from(value: Color) -> Self {
+        match value {
+            Color::Rgb(rgb) => Self::from(rgb),
+            Color::Hsv(hsv) => Self::from(hsv),
+            Color::Hsl(hsl) => hsl,
+            Color::Cmyk(cmyk) => Self::from(Rgb::from(cmyk)),
         }
     }
 }
@@ -90,6 +964,406 @@ impl From<Color> for Rgb {
         match value {
             Color::Rgb(rgb) => rgb,
             Color::Hsv(hsv) => Self::from(hsv),
+            Color::Hsl(hsl) => Self::from(hsl),
+            Color::Cmyk(cmyk) => Self::from(cmyk),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Creates an RGB color.
+    const fn rgb(r: u8, g: u8, b: u8) -> Rgb {
+        Rgb { r, g, b }
+    }
+
+    /// Creates an HSL color.
+    const fn hsl(h: f64, s: f64, l: f64) -> Hsl {
+        Hsl { h, s, l }
+    }
+
+    #[test]
+    fn rgb_to_hsl() {
+        let cases = [
+            (rgb(255, 0, 0), hsl(0.0, 1.0, 0.5)),
+            (rgb(0, 255, 0), hsl(120.0, 1.0, 0.5)),
+            (rgb(0, 0, 255), hsl(240.0, 1.0, 0.5)),
+            (rgb(255, 255, 255), hsl(0.0, 0.0, 1.0)),
+            (rgb(0, 0, 0), hsl(0.0, 0.0, 0.0)),
+            (rgb(0, 128, 128), hsl(180.0, 1.0, 128.0 / 510.0)),
+        ];
+        for (rgb, expected) in cases {
+            let hsl = Hsl::from(rgb);
+            assert!((hsl.h - expected.h).abs() < 1e-9, "{rgb:?}");
+            assert!((hsl.s - expected.s).abs() < 1e-9, "{rgb:?}");
+            assert!((hsl.l - expected.l).abs() < 1e-9, "{rgb:?}");
+        }
+    }
+
+    #[test]
+    fn hsl_to_rgb() {
+        let cases = [
+            (hsl(0.0, 1.0, 0.5), rgb(255, 0, 0)),
+            (hsl(60.0, 1.0, 0.5), rgb(255, 255, 0)),
+            (hsl(0.0, 0.0, 1.0), rgb(255, 255, 255)),
+            (hsl(300.0, 1.0, 0.5), rgb(255, 0, 255)),
+        ];
+        for (hsl, rgb) in cases {
+            assert_eq!(Rgb::from(hsl), rgb, "{hsl:?}");
+            assert_eq!(Rgb::from(Color::Hsl(hsl)), rgb, "{hsl:?}");
+        }
+    }
+
+    #[test]
+    fn rgb_cmyk() {
+        let cmyk = |c, m, y, k| Cmyk { c, m, y, k };
+        let cases = [
+            (rgb(0, 0, 0), cmyk(0.0, 0.0, 0.0, 1.0)),
+            (rgb(255, 255, 255), cmyk(0.0, 0.0, 0.0, 0.0)),
+            (rgb(255, 0, 0), cmyk(0.0, 1.0, 1.0, 0.0)),
+            (rgb(0, 255, 255), cmyk(1.0, 0.0, 0.0, 0.0)),
+            (rgb(51, 102, 204), cmyk(0.75, 0.5, 0.0, 0.2)),
+        ];
+        for (rgb, expected) in cases {
+            let cmyk = Cmyk::from(rgb);
+            assert!((cmyk.c - expected.c).abs() < 1e-9, "{rgb:?}");
+            assert!((cmyk.m - expected.m).abs() < 1e-9, "{rgb:?}");
+            assert!((cmyk.y - expected.y).abs() < 1e-9, "{rgb:?}");
+            assert!((cmyk.k - expected.k).abs() < 1e-9, "{rgb:?}");
+
+            assert_eq!(Rgb::from(cmyk), rgb);
+            assert_eq!(Rgb::from(Color::Cmyk(expected)), rgb);
+        }
+    }
+
+    #[test]
+    fn rgb_hex() {
+        for hex in ["#ffffff", "#000000", "#ff8800"] {
+            assert_eq!(Rgb::from_str(hex).unwrap().to_hex(), hex);
+        }
+        assert_eq!(Rgb::from_str("#f80").unwrap(), rgb(255, 136, 0));
+        assert_eq!(Rgb::from_str("f80").unwrap(), rgb(255, 136, 0));
+        assert_eq!(Rgb::from_str("12AbEf").unwrap(), rgb(0x12, 0xab, 0xef));
+
+        for invalid in ["", "#", "#ff88", "#ff880g", "#+f+f+f"] {
+            assert!(Rgb::from_str(invalid).is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn rgb_round_trip() {
+        let common = [
+            rgb(255, 255, 255),
+            rgb(0, 0, 0),
+            rgb(255, 0, 0),
+            rgb(0, 128, 0),
+            rgb(255, 165, 0),
+            rgb(128, 0, 128),
+            rgb(192, 192, 192),
+        ];
+        // also check a grid over the RGB cube
+        let grid = (0..=255).step_by(15).flat_map(|r| {
+            (0..=255)
+                .step_by(15)
+                .flat_map(move |g| (0..=255).step_by(15).map(move |b| rgb(r, g, b)))
+        });
+        for rgb in common.into_iter().chain(grid) {
+            assert_eq!(Rgb::from(Hsv::from(rgb)), rgb);
+            assert_eq!(Rgb::from(Hsl::from(rgb)), rgb);
+        }
+    }
+
+    #[test]
+    fn hsv_out_of_range() {
+        let hsv = |h, s, v| Hsv { h, s, v };
+        // hues wrap around
+        assert_eq!(Rgb::from(hsv(360.0, 1.0, 1.0)), rgb(255, 0, 0));
+        assert_eq!(
+            Rgb::from(hsv(-30.0, 1.0, 1.0)),
+            Rgb::from(hsv(330.0, 1.0, 1.0))
+        );
+        assert_eq!(Rgb::from(hsv(-30.0, 1.0, 1.0)), rgb(255, 0, 128));
+        assert_eq!(Rgb::from(hsv(480.0, 1.0, 1.0)), rgb(0, 255, 0));
+
+        // saturation and value are clamped
+        assert_eq!(Rgb::from(hsv(0.0, 1.5, 1.0)), rgb(255, 0, 0));
+        assert_eq!(Rgb::from(hsv(0.0, 0.0, 2.0)), rgb(255, 255, 255));
+        assert_eq!(Rgb::from(hsv(0.0, -0.5, 1.0)), rgb(255, 255, 255));
+    }
+
+    #[test]
+    fn luminance() {
+        assert!((rgb(255, 0, 0).luminance() - 0.2126).abs() < 1e-12);
+        assert!((rgb(0, 255, 0).luminance() - 0.7152).abs() < 1e-12);
+        assert!((rgb(0, 0, 255).luminance() - 0.0722).abs() < 1e-12);
+        assert!((rgb(255, 255, 255).luminance() - 1.0).abs() < 1e-12);
+
+        assert_eq!(rgb(255, 0, 0).to_grayscale(), rgb(54, 54, 54));
+        assert_eq!(rgb(0, 255, 0).to_grayscale(), rgb(182, 182, 182));
+        assert_eq!(rgb(0, 0, 255).to_grayscale(), rgb(18, 18, 18));
+    }
+
+    #[test]
+    fn rgb_lab() {
+        let lab = |l, a, b| Lab { l, a, b };
+        let cases = [
+            (rgb(255, 255, 255), lab(100.0, 0.0, 0.0)),
+            (rgb(0, 0, 0), lab(0.0, 0.0, 0.0)),
+            (rgb(128, 128, 128), lab(53.585, 0.0, 0.0)),
+            (rgb(255, 0, 0), lab(53.241, 80.092, 67.203)),
+        ];
+        for (rgb, expected) in cases {
+            let lab = Lab::from(rgb);
+            assert!((lab.l - expected.l).abs() < 0.01, "{rgb:?}: {lab:?}");
+            assert!((lab.a - expected.a).abs() < 0.01, "{rgb:?}: {lab:?}");
+            assert!((lab.b - expected.b).abs() < 0.01, "{rgb:?}: {lab:?}");
+            assert_eq!(Rgb::from(lab), rgb);
+        }
+    }
+
+    #[test]
+    fn rgba_over() {
+        let rgba = |r, g, b, a| Rgba { r, g, b, a };
+        let background = rgba(0, 0, 255, 255);
+
+        // an opaque color hides the background
+        assert_eq!(rgba(255, 0, 0, 255).over(background), rgba(255, 0, 0, 255));
+        // a transparent color shows the background
+        assert_eq!(rgba(255, 0, 0, 0).over(background), background);
+        // nothing over nothing is transparent
+        assert_eq!(rgba(255, 0, 0, 0).over(rgba(0, 0, 255, 0)).a, 0);
+        // half transparent mixes the colors
+        assert_eq!(
+            rgba(255, 0, 0, 128).over(background),
+            rgba(128, 0, 127, 255)
+        );
+
+        assert_eq!(Rgba::from(rgb(1, 2, 3)), rgba(1, 2, 3, 255));
+        assert_eq!(Rgb::from(rgba(1, 2, 3, 4)), rgb(1, 2, 3));
+    }
+
+    #[test]
+    fn named_colors() {
+        assert_eq!(Rgb::from_name("red"), Some(rgb(255, 0, 0)));
+        assert_eq!(Rgb::from_name("BLUE"), Some(rgb(0, 0, 255)));
+        assert_eq!(Rgb::from_name("Olive"), Some(rgb(128, 128, 0)));
+        assert_eq!(Rgb::from_name(" white "), Some(rgb(255, 255, 255)));
+        assert_eq!(Rgb::from_name("bluish"), None);
+        assert_eq!(Rgb::from_name(""), None);
+    }
+
+    #[test]
+    fn blend() {
+        let black = rgb(0, 0, 0);
+        let white = rgb(255, 255, 255);
+        assert_eq!(black.blend(white, 0.5), rgb(128, 128, 128));
+        assert_eq!(black.blend(white, 0.0), black);
+        assert_eq!(black.blend(white, 1.0), white);
+        assert_eq!(black.blend(white, 2.0), white);
+
+        let red = rgb(255, 0, 0);
+        let blue = rgb(0, 0, 255);
+        assert_eq!(red.blend(blue, 0.25), rgb(191, 0, 64));
+    }
+
+    #[test]
+    fn delta_e() {
+        let lab = |l, a, b| Lab { l, a, b };
+        assert!((lab(50.0, 0.0, 0.0).delta_e(&lab(50.0, 3.0, 4.0)) - 5.0).abs() < 1e-12);
+
+        // reference pairs from Sharma, Wu, and Dalal (2005)
+        let pairs = [
+            (
+                lab(50.0, 2.6772, -79.7751),
+                lab(50.0, 0.0, -82.7485),
+                2.0425,
+            ),
+            (
+                lab(50.0, 3.1571, -77.2803),
+                lab(50.0, 0.0, -82.7485),
+                2.8615,
+            ),
+            (lab(50.0, 0.0, 0.0), lab(50.0, -1.0, 2.0), 2.3669),
+            (lab(50.0, 2.5, 0.0), lab(73.0, 25.0, -18.0), 27.1492),
+            (lab(50.0, 2.5, 0.0), lab(50.0, 0.0, -2.5), 4.3065),
+            (
+                lab(60.2574, -34.0099, 36.2677),
+                lab(60.4626, -34.1751, 39.4387),
+                1.2644,
+            ),
+            (
+                lab(22.7233, 20.0904, -46.694),
+                lab(23.0331, 14.973, -42.5619),
+                2.0373,
+            ),
+        ];
+        for (a, b, expected) in pairs {
+            assert!((a.delta_e_2000(&b) - expected).abs() < 1e-4, "{a:?} {b:?}");
+            assert!((b.delta_e_2000(&a) - expected).abs() < 1e-4, "{b:?} {a:?}");
         }
     }
+
+    #[test]
+    fn linear_rgb() {
+        let mid = rgb(128, 64, 200);
+        let linear = mid.to_linear();
+        assert!((linear[0] - 0.215_861).abs() < 1e-6);
+        assert!((linear[1] - 0.051_269).abs() < 1e-6);
+        assert_eq!(Rgb::from_linear(linear), mid);
+
+        assert_eq!(rgb(255, 255, 255).to_linear(), [1.0; 3]);
+        assert_eq!(Rgb::from_linear([0.5, -1.0, 2.0]), rgb(188, 0, 255));
+    }
+
+    #[test]
+    fn hsv_display_from_str() {
+        let hsv = Hsv::from(rgb(255, 136, 17));
+        let formatted = hsv.to_string();
+        assert!(formatted.starts_with("hsv("));
+        assert_eq!(Hsv::from_str(&formatted).unwrap(), hsv);
+
+        let hsv = Hsv {
+            h: 120.0,
+            s: 0.5,
+            v: 1.0,
+        };
+        assert_eq!(hsv.to_string(), "hsv(120, 0.5, 1)");
+        assert_eq!(Hsv::from_str(" hsv(120,0.5, 1) ").unwrap(), hsv);
+
+        for invalid in [
+            "",
+            "hsv()",
+            "hsv(1, 2)",
+            "hsv(1, 2, 3, 4)",
+            "hsl(1, 2, 3)",
+            "hsv(a, b, c)",
+        ] {
+            assert!(Hsv::from_str(invalid).is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn color_hsl() {
+        let hsl = hsl(210.0, 0.6, 0.4);
+        assert_eq!(Rgb::from(Color::Hsl(hsl)), rgb(41, 102, 163));
+
+        let hsv = Hsv::from(Color::Hsl(hsl));
+        assert!((hsv.h - 210.0).abs() < 1e-9);
+        assert!((hsv.s - 0.75).abs() < 1e-9);
+        assert!((hsv.v - 0.64).abs() < 1e-9);
+        assert_eq!(Rgb::from(hsv), rgb(41, 102, 163));
+
+        // converting back to HSL is lossless
+        let round_trip = Hsl::from(Color::Hsv(hsv));
+        assert!((round_trip.s - hsl.s).abs() < 1e-9);
+        assert!((round_trip.l - hsl.l).abs() < 1e-9);
+    }
+
+    #[test]
+    fn rgb_u32() {
+        assert_eq!(rgb(255, 255, 255).to_u32(), 0x00ff_ffff);
+        assert_eq!(Rgb::from(0x00ff_ffff), rgb(255, 255, 255));
+        assert_eq!(rgb(255, 136, 0).to_u32(), 0x00ff_8800);
+        assert_eq!(Rgb::from(0x00ff_8800), rgb(255, 136, 0));
+        // the high bits are ignored
+        assert_eq!(Rgb::from(0xabff_8800), rgb(255, 136, 0));
+    }
+
+    #[test]
+    fn invert_complement() {
+        assert_eq!(rgb(0, 0, 0).invert(), rgb(255, 255, 255));
+        assert_eq!(rgb(255, 136, 0).invert(), rgb(0, 119, 255));
+
+        assert_eq!(rgb(255, 0, 0).complement(), rgb(0, 255, 255));
+        assert_eq!(rgb(0, 0, 255).complement(), rgb(255, 255, 0));
+        // grays have no hue to rotate
+        assert_eq!(rgb(128, 128, 128).complement(), rgb(128, 128, 128));
+    }
+
+    #[test]
+    fn rgb_xyz() {
+        let white = Xyz::from(rgb(255, 255, 255));
+        assert!((white.x - 0.95047).abs() < 1e-5);
+        assert!((white.y - 1.0).abs() < 1e-5);
+        assert!((white.z - 1.08883).abs() < 1e-5);
+        assert_eq!(Rgb::from(white), rgb(255, 255, 255));
+
+        let red = Xyz::from(rgb(255, 0, 0));
+        assert!((red.x - 0.412_456_4).abs() < 1e-5);
+        assert!((red.y - 0.212_672_9).abs() < 1e-5);
+        assert!((red.z - 0.019_333_9).abs() < 1e-5);
+        assert_eq!(Rgb::from(red), rgb(255, 0, 0));
+
+        assert_eq!(Xyz::from(rgb(0, 0, 0)), Xyz::default());
+    }
+
+    #[test]
+    fn rgb_ycbcr() {
+        // grays have no chroma
+        for gray in [0, 64, 128, 255] {
+            let ycbcr = YCbCr::from(rgb(gray, gray, gray));
+            assert!((ycbcr.y - gray as f64).abs() < 1e-9);
+            assert!((ycbcr.cb - 128.0).abs() < 1e-9);
+            assert!((ycbcr.cr - 128.0).abs() < 1e-9);
+        }
+
+        let red = YCbCr::from(rgb(255, 0, 0));
+        assert!((red.y - 76.245).abs() < 1e-9);
+        assert!(red.cr > 128.0 && red.cb < 128.0);
+
+        for color in [
+            rgb(255, 0, 0),
+            rgb(0, 255, 0),
+            rgb(0, 0, 255),
+            rgb(12, 34, 56),
+        ] {
+            assert_eq!(Rgb::from(YCbCr::from(color)), color);
+        }
+    }
+
+    #[test]
+    fn contrast() {
+        let black = rgb(0, 0, 0);
+        let white = rgb(255, 255, 255);
+        assert!((contrast_ratio(black, white) - 21.0).abs() < 1e-9);
+        assert!((contrast_ratio(white, black) - 21.0).abs() < 1e-9);
+        assert!((contrast_ratio(white, white) - 1.0).abs() < 1e-9);
+
+        // mid-gray is darker than half luminance in linear light
+        let gray = rgb(128, 128, 128);
+        assert!((gray.relative_luminance() - 0.2159).abs() < 1e-4);
+        assert!((contrast_ratio(gray, white) - 3.95).abs() < 0.01);
+    }
+
+    #[test]
+    fn sepia() {
+        assert_eq!(rgb(128, 128, 128).sepia(), rgb(173, 154, 120));
+        assert_eq!(rgb(0, 0, 0).sepia(), rgb(0, 0, 0));
+        // bright colors are clamped
+        assert_eq!(rgb(255, 255, 255).sepia(), rgb(255, 255, 239));
+    }
+
+    #[test]
+    fn nearest_name() {
+        assert_eq!(rgb(250, 5, 5).nearest_name(), "red");
+        assert_eq!(rgb(20, 20, 20).nearest_name(), "black");
+        for (name, color) in NAMED_COLORS {
+            assert_eq!(color.nearest_name(), name);
+        }
+    }
+
+    #[test]
+    fn quantize_two_clusters() {
+        let mut colors = Vec::new();
+        for i in 0..10 {
+            colors.push(rgb(250 - i, i, 0));
+            colors.push(rgb(0, i, 250 - i));
+        }
+
+        let mut palette = quantize(&colors, 2, 0);
+        palette.sort_by_key(|c| c.r);
+        assert_eq!(palette, [rgb(0, 5, 246), rgb(246, 5, 0)]);
+    }
 }
@@ -12,6 +12,7 @@ use std::{
 };
 
 #[derive(Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Complex {
     pub re: f64,
     pub im: f64,
@@ -120,7 +121,6 @@ impl Neg for Complex {
     type Output = Self;
 
     #[inline]
-    #[must_use]
     fn neg(self) -> Self::Output {
         self.scale(-1.0)
     }
@@ -138,7 +138,6 @@ impl Add for Complex {
     type Output = Self;
 
     #[inline]
-    #[must_use]
     fn add(mut self, rhs: Self) -> Self::Output {
         self += rhs;
         self
@@ -157,7 +156,6 @@ impl Sub for Complex {
     type Output = Self;
 
     #[inline]
-    #[must_use]
     fn sub(mut self, rhs: Self) -> Self::Output {
         self -= rhs;
         self
@@ -177,7 +175,6 @@ impl Mul for Complex {
     type Output = Self;
 
     #[inline]
-    #[must_use]
     fn mul(mut self, rhs: Self) -> Self::Output {
         self *= rhs;
         self
@@ -199,7 +196,6 @@ impl Div for Complex {
     type Output = Self;
 
     #[inline]
-    #[must_use]
     fn div(mut self, rhs: Self) -> Self::Output {
         self /= rhs;
         self
@@ -208,7 +204,6 @@ impl Div for Complex {
 
 impl Sum for Complex {
     #[inline]
-    #[must_use]
     fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
         iter.fold(Self::default(), |a, b| a + b)
     }
@@ -228,10 +223,17 @@ impl Debug for Complex {
 }
 
 impl Display for Complex {
+    /// Formats the complex number as `a+bi` or `a-bi`, omitting
+    /// the imaginary part if it is 0 and the real part if only
+    /// the imaginary part is nonzero.
     #[inline]
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         if self.im == 0.0 {
             write!(f, "{}", self.re)
+        } else if self.re == 0.0 {
+            write!(f, "{}i", self.im)
+        } else if self.im.is_sign_negative() {
+            write!(f, "{}-{}i", self.re, -self.im)
         } else {
             write!(f, "{}+{}i", self.re, self.im)
         }
@@ -248,10 +250,30 @@ impl From<f64> for Complex {
 impl FromStr for Complex {
     type Err = ParseFloatError;
 
+    /// Parses a complex number of the form `a`, `bi`, `a+bi`, or `a-bi`.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (re, im) = s.split_once('+').unwrap_or((s, "0.0i"));
-        let im = im.split('i').next().unwrap_or("0.0");
-        let (re, im) = (re.parse()?, im.parse()?);
+        let Some(s) = s.strip_suffix('i') else {
+            // no imaginary part
+            return Ok(Self::from(s.parse::<f64>()?));
+        };
+
+        // the imaginary part starts at the first sign after the
+        // real part, which is not the sign of an exponent
+        let bytes = s.as_bytes();
+        let split = (1..bytes.len())
+            .find(|&i| matches!(bytes[i], b'+' | b'-') && !matches!(bytes[i - 1], b'e' | b'E'));
+        let (re, im) = match split {
+            Some(i) => (s[..i].parse()?, &s[i..]),
+            None => (0.0, s),
+        };
+
+        // a lone sign is a coefficient of 1, and a
+        // leading plus may precede a negative number
+        let im = match im {
+            "" | "+" => 1.0,
+            "-" => -1.0,
+            im => im.strip_prefix('+').unwrap_or(im).parse()?,
+        };
         Ok(Self { re, im })
     }
 }
@@ -267,6 +289,35 @@ mod test {
 
         let complex = Complex::from_str("1.0+2.0i").unwrap();
         assert_eq!(complex, Complex { re: 1.0, im: 2.0 });
+
+        let complex = Complex::from_str("-1.0-2.0i").unwrap();
+        assert_eq!(complex, Complex { re: -1.0, im: -2.0 });
+
+        let complex = Complex::from_str("1.0+-2.0i").unwrap();
+        assert_eq!(complex, Complex { re: 1.0, im: -2.0 });
+
+        let complex = Complex::from_str("-2.5i").unwrap();
+        assert_eq!(complex, Complex { re: 0.0, im: -2.5 });
+
+        let complex = Complex::from_str("1e-3-i").unwrap();
+        assert_eq!(complex, Complex { re: 1e-3, im: -1.0 });
+
+        assert!(Complex::from_str("").is_err());
+        assert!(Complex::from_str("1.0+2.0").is_err());
+        assert!(Complex::from_str("1.0+xi").is_err());
+    }
+
+    #[test]
+    fn complex_display() {
+        for (complex, s) in [
+            (Complex { re: 1.5, im: 0.0 }, "1.5"),
+            (Complex { re: 1.5, im: 2.0 }, "1.5+2i"),
+            (Complex { re: 1.5, im: -2.0 }, "1.5-2i"),
+            (Complex { re: 0.0, im: -2.0 }, "-2i"),
+        ] {
+            assert_eq!(complex.to_string(), s);
+            assert_eq!(Complex::from_str(s).unwrap(), complex);
+        }
     }
 
     #[test]
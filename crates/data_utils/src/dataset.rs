@@ -0,0 +1,241 @@
+//! This module provides a collection of data
+//! points with summary statistics.
+//!
+//! Author: Benjamin Hall
+
+use crate::{DataPoint, Point};
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+    ops::Deref,
+};
+
+/// Stores a set of data points.
+///
+/// A data set dereferences to a slice of its data points,
+/// so it can be given directly to classification algorithms.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DataSet<T> {
+    points: Vec<DataPoint<T>>,
+}
+
+/// Calculates the mean of the given points, weighted by their
+/// sample weights, i.e. `∑ wx / ∑ w`.
+///
+/// If the total weight is 0, such as when there are no points,
+/// this returns the zero point rather than dividing by zero.
+pub(crate) fn weighted_mean<'a, I>(points: I) -> Point
+where
+    I: IntoIterator<Item = (&'a Point, f64)>,
+{
+    let mut total_weight = 0.0;
+    let mut sum = Point::default();
+    for (p, w) in points {
+        total_weight += w;
+        sum += p.clone().scale(w);
+    }
+    if total_weight == 0.0 {
+        sum
+    } else {
+        sum.scale(total_weight.recip())
+    }
+}
+
+/// Groups the given data points by classification, with the
+/// classifications in the order they first appear.
+pub(crate) fn group_by_class<T>(data: &[DataPoint<T>]) -> Vec<(&T, Vec<&DataPoint<T>>)>
+where
+    T: PartialEq,
+{
+    let mut groups: Vec<(&T, Vec<&DataPoint<T>>)> = Vec::new();
+    for d in data {
+        match groups.iter_mut().find(|(class, _)| *class == &d.class) {
+            Some((_, group)) => group.push(d),
+            None => groups.push((&d.class, vec![d])),
+        }
+    }
+    groups
+}
+
+impl<T> DataSet<T> {
+    /// Creates a data set from the given data points.
+    #[inline]
+    #[must_use]
+    pub const fn new(points: Vec<DataPoint<T>>) -> Self {
+        Self { points }
+    }
+
+    /// Gets the data points of the data set.
+    #[inline]
+    #[must_use]
+    pub fn points(&self) -> &[DataPoint<T>] {
+        &self.points
+    }
+
+    /// Converts the data set into its data points.
+    #[inline]
+    #[must_use]
+    pub fn into_points(self) -> Vec<DataPoint<T>> {
+        self.points
+    }
+
+    /// Gets the number of dimensions of the first data point,
+    /// or 0 if there is no data.
+    ///
+    /// Use [`validate_dimensions`](crate::validate_dimensions) to
+    /// check that every data point has the same number of dimensions.
+    #[inline]
+    #[must_use]
+    pub fn dim(&self) -> usize {
+        self.points.first().map_or(0, |d| d.point.0.len())
+    }
+
+    /// Calculates the centroid of the data set, the mean of its
+    /// points weighted by their sample weights.
+    #[must_use]
+    pub fn centroid(&self) -> Point {
+        weighted_mean(self.points.iter().map(|d| (&d.point, d.weight)))
+    }
+}
+
+impl<T> DataSet<T>
+where
+    T: Eq + Hash,
+{
+    /// Gets the set of classifications in the data set.
+    #[must_use]
+    pub fn classes(&self) -> HashSet<&T> {
+        self.points.iter().map(|d| &d.class).collect()
+    }
+
+    /// Counts the number of data points with each classification.
+    #[must_use]
+    pub fn class_counts(&self) -> HashMap<&T, usize> {
+        let mut counts = HashMap::new();
+        for d in &self.points {
+            *counts.entry(&d.class).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// Calculates the centroid of each classification, the mean of
+    /// its points weighted by their sample weights.
+    #[must_use]
+    pub fn per_class_centroids(&self) -> HashMap<&T, Point> {
+        group_by_class(&self.points)
+            .into_iter()
+            .map(|(class, data)| {
+                (
+                    class,
+                    weighted_mean(data.into_iter().map(|d| (&d.point, d.weight))),
+                )
+            })
+            .collect()
+    }
+}
+
+impl<T> Deref for DataSet<T> {
+    type Target = [DataPoint<T>];
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.points
+    }
+}
+
+impl<T> From<Vec<DataPoint<T>>> for DataSet<T> {
+    #[inline]
+    fn from(value: Vec<DataPoint<T>>) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T> FromIterator<DataPoint<T>> for DataSet<T> {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = DataPoint<T>>>(iter: I) -> Self {
+        Self::new(iter.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Creates a small labeled data set.
+    fn data_set() -> DataSet<String> {
+        [
+            "0.0 0.0 red",
+            "2.0 0.0 red",
+            "1.0 3.0 red",
+            "10.0 10.0 blue",
+            "12.0 10.0 blue",
+        ]
+        .into_iter()
+        .map(|line| line.parse().unwrap())
+        .collect()
+    }
+
+    #[test]
+    fn class_counts() {
+        let data = data_set();
+        assert_eq!(data.len(), 5);
+        assert_eq!(data.dim(), 2);
+        assert_eq!(
+            data.classes(),
+            HashSet::from([&String::from("red"), &String::from("blue")])
+        );
+
+        let counts = data.class_counts();
+        assert_eq!(counts[&String::from("red")], 3);
+        assert_eq!(counts[&String::from("blue")], 2);
+    }
+
+    #[test]
+    fn centroids() {
+        let data = data_set();
+        let centroid = data.centroid();
+        assert!((centroid.0[0].re - 5.0).abs() < 1e-12);
+        assert!((centroid.0[1].re - 4.6).abs() < 1e-12);
+
+        let centroids = data.per_class_centroids();
+        assert_eq!(centroids.len(), 2);
+        assert_eq!(
+            centroids[&String::from("red")],
+            Point(vec![1.0.into(), 1.0.into()])
+        );
+        assert_eq!(
+            centroids[&String::from("blue")],
+            Point(vec![11.0.into(), 10.0.into()])
+        );
+
+        assert_eq!(DataSet::<String>::default().centroid(), Point::default());
+    }
+
+    #[test]
+    fn zero_weight_mean() {
+        let a = Point(vec![1.0.into(), 2.0.into()]);
+        let b = Point(vec![3.0.into(), 4.0.into()]);
+        assert_eq!(
+            weighted_mean([(&a, 1.0), (&b, 3.0)]),
+            Point(vec![2.5.into(), 3.5.into()])
+        );
+
+        // no weight gives the zero point rather than dividing by zero
+        assert_eq!(
+            weighted_mean([(&a, 0.0), (&b, 0.0)]),
+            Point(vec![0.0.into(), 0.0.into()])
+        );
+        assert_eq!(weighted_mean([]), Point::default());
+    }
+
+    #[test]
+    fn groups() {
+        let data = data_set();
+        let groups = group_by_class(&data);
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].0, "red");
+        assert_eq!(groups[0].1.len(), 3);
+        assert_eq!(groups[1].0, "blue");
+        assert_eq!(groups[1].1, [&data[3], &data[4]]);
+    }
+}
@@ -3,20 +3,495 @@
 //!
 //! Author: Benjamin Hall
 
+use crate::DataPoint;
 use std::{
+    error::Error,
+    fmt::{self, Display},
     fs::File,
-    io::{self, BufReader, Read},
+    io::{self, BufRead, BufReader, BufWriter, Read, Write},
+    path::Path,
+    str::FromStr,
 };
 
+/// Loads data from a reader, such as stdin or a byte slice, into a String.
+///
+/// Any errors, such as the data not being valid UTF-8,
+/// will be propagated up to the caller.
+#[inline]
+pub fn read_to_string<R: Read>(reader: R) -> io::Result<String> {
+    let mut buffer = String::new();
+    let mut reader = BufReader::new(reader);
+    reader.read_to_string(&mut buffer).map(|_| buffer)
+}
+
 /// Loads data from a file into a String.
 ///
 /// Any errors, such as the file not existing or not having
 /// read access, will be propagated up to the caller.
 #[inline]
 pub fn read_file(file: io::Result<File>) -> io::Result<String> {
-    let file = file?;
+    read_to_string(file?)
+}
 
-    let mut buffer = String::new();
-    let mut reader = BufReader::new(file);
-    reader.read_to_string(&mut buffer).map(|_| buffer)
+/// Opens a file and loads its data into a String.
+///
+/// Any errors, such as the file not existing or not having read
+/// access, will be propagated up to the caller with the path
+/// included in the error message.
+pub fn read_file_from_path<P: AsRef<Path>>(path: P) -> io::Result<String> {
+    let path = path.as_ref();
+    read_file(File::open(path)).map_err(|e| error_with_path(&e, path))
+}
+
+/// Loads data from a file into a String, where a file
+/// name of `-` loads the data from stdin instead.
+///
+/// Any errors will be propagated up to the caller, with the
+/// file name included in the error message for files.
+pub fn read_file_or_stdin(file_name: &str) -> io::Result<String> {
+    if file_name == "-" {
+        read_to_string(io::stdin().lock())
+    } else {
+        read_file_from_path(file_name)
+    }
+}
+
+/// Opens a gzip-compressed file and loads its decompressed data into a String.
+///
+/// Any errors, such as the file not existing or not being valid
+/// gzip data, will be propagated up to the caller with the path
+/// included in the error message.
+#[cfg(feature = "gzip")]
+pub fn read_file_gz<P: AsRef<Path>>(path: P) -> io::Result<String> {
+    let path = path.as_ref();
+    File::open(path)
+        .and_then(|file| read_to_string(flate2::read::GzDecoder::new(file)))
+        .map_err(|e| error_with_path(&e, path))
+}
+
+/// Adds a path to the message of an IO error, keeping its kind.
+#[inline]
+fn error_with_path(e: &io::Error, path: &Path) -> io::Error {
+    io::Error::new(e.kind(), format!("{}: {e}", path.display()))
+}
+
+/// An error parsing a line of data points.
+#[derive(Debug)]
+pub struct ParseError {
+    /// The line number of the line, starting at 1
+    pub line: usize,
+    /// The text of the line
+    pub text: String,
+    /// The error parsing the line
+    source: Box<dyn Error>,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}: could not parse \"{}\": {}",
+            self.line, self.text, self.source
+        )
+    }
+}
+
+impl Error for ParseError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(self.source.as_ref())
+    }
+}
+
+/// Configures which lines of a data file are skipped when reading data points.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ReadOptions {
+    /// Whether to skip the first line, such as a header row of column names
+    pub skip_header: bool,
+    /// Whether to skip lines beginning with `#`
+    pub skip_comments: bool,
+    /// Whether the first value of each line is the
+    /// identifier of the data point, such as a row ID
+    pub id_column: bool,
+}
+
+/// Parses data points from a String, one per line.
+///
+/// If a line cannot be parsed, the returned error
+/// includes the line number and text of the line.
+#[inline]
+pub fn parse_data_points<T>(contents: &str) -> Result<Vec<DataPoint<T>>, ParseError>
+where
+    T: FromStr,
+    <T as FromStr>::Err: Error + 'static,
+{
+    parse_data_points_with(contents, &ReadOptions::default())
+}
+
+/// Parses data points from a String, one per line,
+/// skipping the lines given by the options.
+///
+/// If a line cannot be parsed, the returned error includes the
+/// line number and text of the line, counting skipped lines.
+pub fn parse_data_points_with<T>(
+    contents: &str,
+    options: &ReadOptions,
+) -> Result<Vec<DataPoint<T>>, ParseError>
+where
+    T: FromStr,
+    <T as FromStr>::Err: Error + 'static,
+{
+    contents
+        .lines()
+        .enumerate()
+        .skip(usize::from(options.skip_header))
+        .filter(|(_, line)| !(options.skip_comments && line.trim_start().starts_with('#')))
+        .map(|(i, line)| {
+            parse_line(line, options).map_err(|source| ParseError {
+                line: i + 1,
+                text: line.to_owned(),
+                source,
+            })
+        })
+        .collect()
+}
+
+/// Parses a data point from a line, reading
+/// its identifier if the options include one.
+fn parse_line<T>(line: &str, options: &ReadOptions) -> Result<DataPoint<T>, Box<dyn Error>>
+where
+    T: FromStr,
+    <T as FromStr>::Err: Error + 'static,
+{
+    if !options.id_column {
+        return DataPoint::try_from(line);
+    }
+
+    // the identifier is the first value
+    let Some((id, rest)) = line.trim_start().split_once(char::is_whitespace) else {
+        return Err("Cannot parse line of data with only an identifier".into());
+    };
+    let mut data = DataPoint::try_from(rest)?;
+    data.id = Some(id.to_owned());
+    Ok(data)
+}
+
+/// Reads and parses data points from several files, one per line,
+/// concatenating the data points in the order of the files.
+///
+/// Any errors reading or parsing a file will be propagated up to
+/// the caller with the path of the file included in the error message.
+pub fn read_data_points_many<T, P>(paths: &[P]) -> Result<Vec<DataPoint<T>>, Box<dyn Error>>
+where
+    T: FromStr,
+    <T as FromStr>::Err: Error + 'static,
+    P: AsRef<Path>,
+{
+    let mut points = Vec::new();
+    for path in paths {
+        let path = path.as_ref();
+        let contents = read_file_from_path(path)?;
+        let file_points =
+            parse_data_points(&contents).map_err(|e| format!("{}: {e}", path.display()))?;
+        points.extend(file_points);
+    }
+    Ok(points)
+}
+
+/// Opens a file of data points, one per line, returning an iterator
+/// that reads and parses each line as it is needed.
+///
+/// Unlike [`read_file`], the file is never loaded into memory all at
+/// once. Errors opening the file are returned immediately, while errors
+/// reading or parsing a line are returned by the iterator.
+pub fn read_data_points<T, P>(
+    path: P,
+) -> io::Result<impl Iterator<Item = Result<DataPoint<T>, Box<dyn Error>>>>
+where
+    T: FromStr,
+    <T as FromStr>::Err: Error + 'static,
+    P: AsRef<Path>,
+{
+    let reader = BufReader::new(File::open(path)?);
+    Ok(reader
+        .lines()
+        .map(|line| DataPoint::try_from(line?.as_str())))
+}
+
+/// Opens a file of data points like [`read_data_points`], calling
+/// `on_line` with the running count of parsed data points as each
+/// line is parsed, such as to report progress.
+pub fn read_data_points_with_progress<T, P>(
+    path: P,
+    mut on_line: impl FnMut(usize),
+) -> io::Result<impl Iterator<Item = Result<DataPoint<T>, Box<dyn Error>>>>
+where
+    T: FromStr,
+    <T as FromStr>::Err: Error + 'static,
+    P: AsRef<Path>,
+{
+    let mut cnt = 0;
+    Ok(read_data_points(path)?.inspect(move |point| {
+        if point.is_ok() {
+            cnt += 1;
+            on_line(cnt);
+        }
+    }))
+}
+
+/// Creates a file for writing, wrapped in a buffered writer.
+///
+/// The file is created if it does not exist, or truncated if it
+/// does. Any errors will be propagated up to the caller.
+#[inline]
+pub fn create_buffered_writer<P: AsRef<Path>>(path: P) -> io::Result<BufWriter<File>> {
+    File::create(path).map(BufWriter::new)
+}
+
+/// Writes data points to a file, one per line.
+///
+/// Each line is the components of the point followed by the classification,
+/// which can be read back with [`parse_data_points`]. Sample weights and
+/// identifiers are not written, so they are lost when the file is read.
+///
+/// The file is created if it does not exist, or truncated if it
+/// does. Any errors will be propagated up to the caller.
+pub fn write_data_points<T, P>(path: P, points: &[DataPoint<T>]) -> io::Result<()>
+where
+    T: Display,
+    P: AsRef<Path>,
+{
+    let mut writer = create_buffered_writer(path)?;
+    for point in points {
+        writeln!(writer, "{point}")?;
+    }
+    writer.flush()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Complex, Point};
+    use std::{env, fs, io::Cursor, path::PathBuf};
+
+    /// Creates a path in the temp directory unique to this process.
+    fn temp_path(name: &str) -> PathBuf {
+        env::temp_dir().join(format!("data_utils_{}_{name}", std::process::id()))
+    }
+
+    #[test]
+    fn read_from_reader() {
+        let contents = read_to_string(Cursor::new(b"0.1 0.2 red\n".as_slice()));
+        assert_eq!(contents.unwrap(), "0.1 0.2 red\n");
+
+        let err = read_to_string(Cursor::new([0xff, 0xfe].as_slice())).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn read_from_path() {
+        let path = temp_path("read_from_path.txt");
+        fs::write(&path, "0.1 0.2 red\n").unwrap();
+
+        let contents = read_file_from_path(&path);
+        fs::remove_file(&path).unwrap();
+        assert_eq!(contents.unwrap(), "0.1 0.2 red\n");
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn read_gz() {
+        use flate2::{write::GzEncoder, Compression};
+
+        let contents = "0.1 0.2 red\n0.3 0.4 blue\n";
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(contents.as_bytes()).unwrap();
+
+        let path = temp_path("read_gz.txt.gz");
+        fs::write(&path, encoder.finish().unwrap()).unwrap();
+        let read_contents = read_file_gz(&path);
+        fs::remove_file(&path).unwrap();
+        assert_eq!(read_contents.unwrap(), contents);
+
+        // plaintext is not valid gzip data
+        let path = temp_path("read_gz_plain.txt");
+        fs::write(&path, contents).unwrap();
+        let read_contents = read_file_gz(&path);
+        fs::remove_file(&path).unwrap();
+        assert!(read_contents.is_err());
+    }
+
+    #[test]
+    fn read_missing_file() {
+        let path = temp_path("missing.txt");
+        let err = read_file_from_path(&path).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+        assert!(err.to_string().contains(&*path.to_string_lossy()));
+
+        let err = read_file_or_stdin(path.to_str().unwrap()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+        assert!(err.to_string().contains(&*path.to_string_lossy()));
+    }
+
+    #[test]
+    fn buffered_writer() {
+        let path = temp_path("buffered_writer.txt");
+        fs::write(&path, "old contents that are longer\n").unwrap();
+
+        let mut writer = create_buffered_writer(&path).unwrap();
+        writeln!(writer, "new").unwrap();
+        writer.flush().unwrap();
+        drop(writer);
+
+        let contents = fs::read_to_string(&path);
+        fs::remove_file(&path).unwrap();
+        assert_eq!(contents.unwrap(), "new\n");
+    }
+
+    #[test]
+    fn write_read_data_points() {
+        let points = vec![
+            DataPoint {
+                point: Point(vec![Complex { re: 0.5, im: 0.25 }, 1.0.into()]),
+                class: String::from("red"),
+                weight: 1.0,
+                id: None,
+            },
+            DataPoint {
+                point: Point(vec![0.1.into(), 0.2.into()]),
+                class: String::from("blue"),
+                weight: 1.0,
+                id: None,
+            },
+        ];
+
+        let path = temp_path("write_read_data_points.txt");
+        write_data_points(&path, &points).unwrap();
+        let contents = read_file_from_path(&path);
+        fs::remove_file(&path).unwrap();
+
+        let read_points: Vec<_> = contents
+            .unwrap()
+            .lines()
+            .map(|line| DataPoint::<String>::try_from(line).unwrap())
+            .collect();
+        assert_eq!(read_points, points);
+    }
+
+    #[test]
+    fn parse_error_line() {
+        let contents = "0.1 0.2 red\n0.3 0.4 blue\n0.5 oops green\n0.6 0.7 red\n";
+        let err = parse_data_points::<String>(contents).unwrap_err();
+        assert_eq!(err.line, 3);
+        assert_eq!(err.text, "0.5 oops green");
+        assert!(err.to_string().starts_with("line 3: "));
+
+        let points = parse_data_points::<String>("0.1 0.2 red\n0.3 0.4 blue").unwrap();
+        assert_eq!(points.len(), 2);
+    }
+
+    #[test]
+    fn skip_header_comments() {
+        let contents = "r g b class\n# first comment\n0.1 0.2 0.3 red\n  # indented comment\n0.4 0.5 0.6 blue\n";
+        let options = ReadOptions {
+            skip_header: true,
+            skip_comments: true,
+            ..ReadOptions::default()
+        };
+        let points = parse_data_points_with::<String>(contents, &options).unwrap();
+        let classes: Vec<_> = points.iter().map(|d| d.class.as_str()).collect();
+        assert_eq!(classes, ["red", "blue"]);
+
+        // without skipping, the header fails to parse
+        let err = parse_data_points::<String>(contents).unwrap_err();
+        assert_eq!(err.line, 1);
+
+        // without skipping comments, the line number counts the header
+        let options = ReadOptions {
+            skip_header: true,
+            ..ReadOptions::default()
+        };
+        let err = parse_data_points_with::<String>(contents, &options).unwrap_err();
+        assert_eq!(err.line, 2);
+    }
+
+    #[test]
+    fn read_many() {
+        let paths = [temp_path("read_many_1.txt"), temp_path("read_many_2.txt")];
+        fs::write(&paths[0], "0.1 0.2 red\n0.3 0.4 blue\n").unwrap();
+        fs::write(&paths[1], "0.5 0.6 green\n").unwrap();
+        let points = read_data_points_many::<String, _>(&paths);
+
+        // a file that fails to parse is named in the error
+        let bad_path = temp_path("read_many_bad.txt");
+        fs::write(&bad_path, "0.1 0.2 red\nbad line\n").unwrap();
+        let err = read_data_points_many::<String, _>(&[&paths[0], &bad_path]).unwrap_err();
+        for path in paths.iter().chain([&bad_path]) {
+            fs::remove_file(path).unwrap();
+        }
+
+        let classes: Vec<_> = points.unwrap().into_iter().map(|d| d.class).collect();
+        assert_eq!(classes, ["red", "blue", "green"]);
+        assert!(err.to_string().contains(&*bad_path.to_string_lossy()));
+        assert!(err.to_string().contains("line 2"));
+    }
+
+    #[test]
+    fn ids_through_knn() {
+        let options = ReadOptions {
+            id_column: true,
+            ..ReadOptions::default()
+        };
+        let train_data =
+            parse_data_points_with::<String>("a 0.0 0.0 red\nb 10.0 10.0 blue\n", &options)
+                .unwrap();
+        let test_data =
+            parse_data_points_with::<String>("row1 1.0 1.0 red\nrow2 9.0 9.0 blue\n", &options)
+                .unwrap();
+        assert_eq!(train_data[1].id.as_deref(), Some("b"));
+        assert_eq!(train_data[1].point, Point(vec![10.0.into(), 10.0.into()]));
+
+        let results = crate::classify::k_nearest_neighbor(&train_data, &test_data, 1);
+        let ids: Vec<_> = results
+            .iter()
+            .map(|c| (c.id(), c.class_guess.as_str()))
+            .collect();
+        assert_eq!(ids, [(Some("row1"), "red"), (Some("row2"), "blue")]);
+
+        // a line with only an identifier cannot be parsed
+        assert!(parse_data_points_with::<String>("row1\n", &options).is_err());
+    }
+
+    #[test]
+    fn stream_data_points() {
+        let path = temp_path("stream_data_points.txt");
+        fs::write(&path, "0.1 0.2 red\n0.3 0.4 blue\n0.5 green\n").unwrap();
+
+        let points: Result<Vec<_>, _> = read_data_points::<String, _>(&path).unwrap().collect();
+        fs::remove_file(&path).unwrap();
+
+        let points = points.unwrap();
+        let classes: Vec<_> = points.iter().map(|d| d.class.as_str()).collect();
+        assert_eq!(classes, ["red", "blue", "green"]);
+        assert_eq!(points[1].point, Point(vec![0.3.into(), 0.4.into()]));
+        assert_eq!(points[2].point, Point(vec![0.5.into()]));
+
+        assert!(read_data_points::<String, _>(temp_path("missing_points.txt")).is_err());
+    }
+
+    #[test]
+    fn read_progress() {
+        let path = temp_path("read_progress.txt");
+        fs::write(&path, "0.1 red\n0.2 blue\n0.3 green\n").unwrap();
+
+        let mut counts = Vec::new();
+        let points: Result<Vec<_>, _> =
+            read_data_points_with_progress::<String, _>(&path, |cnt| counts.push(cnt))
+                .unwrap()
+                .collect();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(points.unwrap().len(), 3);
+        assert_eq!(counts, [1, 2, 3]);
+    }
 }
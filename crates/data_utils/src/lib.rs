@@ -3,14 +3,18 @@
 //!
 //! Author: Benjamin Hall
 
+pub mod args;
 pub mod classify;
 pub mod color;
 pub mod complex;
+pub mod dataset;
 pub mod io;
 pub mod lin_alg;
+pub mod prelude;
 pub mod sort;
 
 pub use complex::Complex;
+pub use dataset::DataSet;
 pub use lin_alg::Point;
 use std::{
     error::Error,
@@ -20,23 +24,128 @@ use std::{
 
 /// Stores a point of data.
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DataPoint<T> {
     // The data point, represented as a point vector
     pub point: Point,
     // The data's classification
     pub class: T,
+    // The data's sample weight, which is 1 unless the
+    // data should count more or less than other data
+    #[cfg_attr(feature = "serde", serde(default = "default_weight"))]
+    pub weight: f64,
+    // The data's identifier, such as the ID of the record it was read
+    // from, which is carried through classification unchanged
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub id: Option<String>,
 }
 
-impl<T> TryFrom<&str> for DataPoint<T>
+/// The sample weight of data that does not specify one.
+#[cfg(feature = "serde")]
+#[inline]
+const fn default_weight() -> f64 {
+    1.0
+}
+
+impl<T> DataPoint<T> {
+    /// Transforms the classification of the data point, keeping the point.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    /// ```
+    /// # use data_utils::DataPoint;
+    /// let data = "0.1 0.2 red".parse::<DataPoint<String>>().unwrap();
+    /// let data = data.map_class(|class| class == "red");
+    /// assert!(data.class);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn map_class<U, F>(self, f: F) -> DataPoint<U>
+    where
+        F: FnOnce(T) -> U,
+    {
+        DataPoint {
+            point: self.point,
+            class: f(self.class),
+            weight: self.weight,
+            id: self.id,
+        }
+    }
+
+    /// Scales the point of the data point to a magnitude of 1,
+    /// keeping its classification. A point with a magnitude
+    /// of 0 is unchanged.
+    #[inline]
+    pub fn normalize(&mut self) {
+        self.point = std::mem::take(&mut self.point).normalize();
+    }
+
+    /// Calculates the Euclidean distance between the points
+    /// of two data points, ignoring their classifications.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    /// ```
+    /// # use data_utils::DataPoint;
+    /// let a = "0.0 0.0 red".parse::<DataPoint<String>>().unwrap();
+    /// let b = "3.0 4.0 blue".parse::<DataPoint<String>>().unwrap();
+    /// assert!((a.distance(&b) - 5.0).abs() < f64::EPSILON);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn distance(&self, other: &Self) -> f64 {
+        (&self.point - &other.point).magnitude()
+    }
+}
+
+impl<T> DataPoint<T>
 where
     T: FromStr,
     <T as FromStr>::Err: std::error::Error + 'static,
 {
-    type Error = Box<dyn Error>;
+    /// Parses a data point from a line of fields separated by the given
+    /// delimiter, or by any whitespace if there is no delimiter.
+    /// Whitespace around each field is ignored.
+    ///
+    /// The last field is the classification and the other fields are
+    /// the point. A delimiter of `','` parses CSV files, and `'\t'`
+    /// parses TSV files.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    /// ```
+    /// # use data_utils::{DataPoint, Point};
+    /// let data = DataPoint::<String>::try_from_delimited("0.1, 0.2, red", Some(',')).unwrap();
+    /// assert_eq!(data.point, Point(vec![0.1.into(), 0.2.into()]));
+    /// assert_eq!(data.class, "red");
+    ///
+    /// let data = DataPoint::<String>::try_from_delimited("0.1  0.2 red", None).unwrap();
+    /// assert_eq!(data.point, Point(vec![0.1.into(), 0.2.into()]));
+    /// ```
+    pub fn try_from_delimited(line: &str, sep: Option<char>) -> Result<Self, Box<dyn Error>> {
+        match sep {
+            Some(_) if line.trim().is_empty() => Err("Cannot parse empty line of data".into()),
+            Some(sep) => Self::from_fields(line.split(sep).map(str::trim).collect()),
+            // split on whitespace
+            None => Self::from_fields(line.split_whitespace().collect()),
+        }
+    }
 
-    fn try_from(value: &str) -> Result<Self, Self::Error> {
-        // split on whitespace
-        let data: Vec<_> = value.split_whitespace().collect();
+    /// Parses a data point from a line of fields separated by the
+    /// given delimiter, such as a line of a CSV file.
+    ///
+    /// This is [`DataPoint::try_from_delimited`] with a delimiter.
+    #[inline]
+    pub fn from_delimited(line: &str, delimiter: char) -> Result<Self, Box<dyn Error>> {
+        Self::try_from_delimited(line, Some(delimiter))
+    }
+
+    /// Parses a data point from its fields, where the last
+    /// field is the classification.
+    fn from_fields(data: Vec<&str>) -> Result<Self, Box<dyn Error>> {
         // last element is classification
         let Some((&last, rest)) = data.split_last() else {
             return Err("Cannot parse empty line of data".into());
@@ -52,7 +161,38 @@ where
         // wrap displacement vector in a Point
         let point = Point(point);
         // return the DataPoint
-        Ok(Self { point, class })
+        Ok(Self {
+            point,
+            class,
+            weight: 1.0,
+            id: None,
+        })
+    }
+}
+
+impl<T> TryFrom<&str> for DataPoint<T>
+where
+    T: FromStr,
+    <T as FromStr>::Err: std::error::Error + 'static,
+{
+    type Error = Box<dyn Error>;
+
+    #[inline]
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Self::try_from_delimited(value, None)
+    }
+}
+
+impl<T> FromStr for DataPoint<T>
+where
+    T: FromStr,
+    <T as FromStr>::Err: std::error::Error + 'static,
+{
+    type Err = Box<dyn Error>;
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from(s)
     }
 }
 
@@ -60,6 +200,12 @@ impl<T> Display for DataPoint<T>
 where
     T: Display,
 {
+    /// Formats the data point as a line of data, the components of the
+    /// point followed by the classification, which parses back into the
+    /// same point and classification.
+    ///
+    /// The sample weight and identifier are not written, so a parsed
+    /// data point has a weight of 1 and no identifier.
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         for v in &self.point.0 {
             write!(f, "{}   ", *v)?;
@@ -68,10 +214,55 @@ where
     }
 }
 
+/// Checks that every data point has the same number of dimensions,
+/// returning the number of dimensions, or 0 if there is no data.
+///
+/// # Errors
+///
+/// Returns an error naming the index of the first data point
+/// with a different number of dimensions than the first.
+pub fn validate_dimensions<T>(data: &[DataPoint<T>]) -> Result<usize, String> {
+    let Some(first) = data.first() else {
+        return Ok(0);
+    };
+
+    let dim = first.point.0.len();
+    match data.iter().position(|d| d.point.0.len() != dim) {
+        Some(i) => Err(format!(
+            "data point {i} has {} dimensions, expected {dim}",
+            data[i].point.0.len()
+        )),
+        None => Ok(dim),
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
 
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialize_defaults() {
+        // data serialized before sample weights and identifiers existed
+        let json = r#"{"point":[{"re":0.5,"im":0.0}],"class":"red"}"#;
+        let data: DataPoint<String> = serde_json::from_str(json).unwrap();
+        assert_eq!(data.point, Point(vec![0.5.into()]));
+        assert!((data.weight - 1.0).abs() < f64::EPSILON);
+        assert_eq!(data.id, None);
+
+        let data = DataPoint {
+            point: Point(vec![0.5.into()]),
+            class: String::from("red"),
+            weight: 2.0,
+            id: Some(String::from("a")),
+        };
+        let json = serde_json::to_string(&data).unwrap();
+        assert_eq!(
+            serde_json::from_str::<DataPoint<String>>(&json).unwrap(),
+            data
+        );
+    }
+
     #[test]
     fn data_point_from_str() {
         let data = DataPoint::<String>::try_from("0.954+0.3i   1.0   0.7   red").unwrap();
@@ -82,7 +273,154 @@ mod test {
                 Complex::from_polar(0.7, 0.0),
             ]),
             class: String::from("red"),
+            weight: 1.0,
+            id: None,
+        };
+        assert_eq!(data, expected);
+    }
+
+    #[test]
+    fn data_point_from_csv() {
+        let data = DataPoint::<String>::from_delimited("0.1,0.2,0.3,red", ',').unwrap();
+        let expected = DataPoint::<String> {
+            point: Point(vec![0.1.into(), 0.2.into(), 0.3.into()]),
+            class: String::from("red"),
+            weight: 1.0,
+            id: None,
+        };
+        assert_eq!(data, expected);
+
+        // whitespace around fields is ignored
+        let data = DataPoint::<String>::from_delimited(" 0.1, 0.2 ,0.3 , red \n", ',').unwrap();
+        assert_eq!(data, expected);
+
+        assert!(DataPoint::<String>::from_delimited("", ',').is_err());
+        assert!(DataPoint::<String>::from_delimited("0.1,,red", ',').is_err());
+    }
+
+    #[test]
+    fn data_point_delimiters() {
+        let expected = DataPoint::<String> {
+            point: Point(vec![0.1.into(), 0.2.into()]),
+            class: String::from("red"),
+            weight: 1.0,
+            id: None,
+        };
+        for (line, sep) in [
+            ("0.1   0.2\tred", None),
+            ("0.1,0.2, red", Some(',')),
+            ("0.1\t0.2\tred", Some('\t')),
+        ] {
+            assert_eq!(DataPoint::try_from_delimited(line, sep).unwrap(), expected);
+        }
+
+        // a tab delimiter keeps spaces within the fields
+        assert!(DataPoint::<String>::try_from_delimited("0.1 0.2\tred", Some('\t')).is_err());
+    }
+
+    #[test]
+    fn data_point_parse() {
+        let data = "0.1 0.2 red".parse::<DataPoint<String>>().unwrap();
+        let expected = DataPoint::<String> {
+            point: Point(vec![0.1.into(), 0.2.into()]),
+            class: String::from("red"),
+            weight: 1.0,
+            id: None,
         };
         assert_eq!(data, expected);
     }
+
+    #[test]
+    fn data_point_display_round_trip() {
+        let mut rng = fastrand::Rng::with_seed(0);
+        // random components, which may be real, imaginary, or
+        // complex, and may have negative parts
+        let mut component = || {
+            let mut part = || match rng.u8(..4) {
+                0 => 0.0,
+                _ => rng.f64().mul_add(200.0, -100.0),
+            };
+            Complex {
+                re: part(),
+                im: part(),
+            }
+        };
+
+        for i in 0..100 {
+            let data = DataPoint {
+                point: Point((0..i % 5 + 1).map(|_| component()).collect()),
+                class: format!("class{i}"),
+                weight: 1.0,
+                id: None,
+            };
+            let parsed = data.to_string().parse::<DataPoint<String>>().unwrap();
+            assert_eq!(parsed, data);
+        }
+
+        // the sample weight and identifier are not written
+        let data = DataPoint {
+            point: Point(vec![1.0.into(), Complex { re: 0.5, im: -2.0 }]),
+            class: String::from("red"),
+            weight: 3.0,
+            id: Some(String::from("a")),
+        };
+        let parsed = data.to_string().parse::<DataPoint<String>>().unwrap();
+        assert_eq!(parsed.point, data.point);
+        assert_eq!(parsed.class, data.class);
+        assert!((parsed.weight - 1.0).abs() < f64::EPSILON);
+        assert_eq!(parsed.id, None);
+    }
+
+    #[test]
+    fn data_point_map_class() {
+        #[derive(Debug, PartialEq)]
+        enum Label {
+            Red,
+            Blue,
+        }
+
+        let data: Vec<_> = ["0.1 0.2 red", "0.3 0.4 blue"]
+            .into_iter()
+            .map(|line| line.parse::<DataPoint<String>>().unwrap())
+            .map(|d| {
+                d.map_class(|class| match class.as_str() {
+                    "red" => Label::Red,
+                    _ => Label::Blue,
+                })
+            })
+            .collect();
+
+        assert_eq!(data[0].class, Label::Red);
+        assert_eq!(data[1].class, Label::Blue);
+        assert_eq!(data[1].point, Point(vec![0.3.into(), 0.4.into()]));
+    }
+
+    #[test]
+    fn data_dimensions() {
+        let data: Vec<_> = ["0.1 0.2 red", "0.3 0.4 blue", "0.5 green", "0.6 0.7 red"]
+            .into_iter()
+            .map(|line| line.parse::<DataPoint<String>>().unwrap())
+            .collect();
+
+        assert_eq!(validate_dimensions(&data[..2]), Ok(2));
+        assert_eq!(
+            validate_dimensions(&data),
+            Err(String::from("data point 2 has 1 dimensions, expected 2"))
+        );
+        assert_eq!(validate_dimensions::<String>(&[]), Ok(0));
+    }
+
+    #[test]
+    fn data_point_normalize() {
+        let mut data = "1.0 2.0+2.0i 4.0 red".parse::<DataPoint<String>>().unwrap();
+        data.normalize();
+        assert!((data.point.magnitude() - 1.0).abs() < 1e-12);
+        assert!((data.point.0[0].re - 0.2).abs() < 1e-12);
+        assert_eq!(data.class, "red");
+
+        // a zero point cannot be normalized
+        let mut data = "0.0 0.0 blue".parse::<DataPoint<String>>().unwrap();
+        data.normalize();
+        assert_eq!(data.point, Point(vec![0.0.into(), 0.0.into()]));
+    }
 }
@@ -4,15 +4,18 @@
 //! Author: Benjamin Hall
 
 use std::{
-    fmt::Debug,
+    error::Error,
+    fmt::{Debug, Display},
     iter::Sum,
-    ops::{Add, AddAssign, Neg, Sub, SubAssign},
+    ops::{Add, AddAssign, Index, IndexMut, Mul, Neg, Sub, SubAssign},
+    str::FromStr,
 };
 
 use crate::Complex;
 
 /// Stores a point vector.
 #[derive(Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Point(pub Vec<Complex>);
 
 impl Debug for Point {
@@ -64,6 +67,28 @@ impl Point {
         self
     }
 
+    /// Scales the point to a magnitude of 1.
+    /// A point with a magnitude of 0 is unchanged.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    /// ```
+    /// # use data_utils::Point;
+    /// let point = Point(vec![3.0.into(), 4.0.into()]).normalize();
+    /// assert!((point.magnitude() - 1.0).abs() < f64::EPSILON);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn normalize(self) -> Self {
+        let magnitude = self.magnitude();
+        if magnitude == 0.0 {
+            self
+        } else {
+            self.scale(magnitude.recip())
+        }
+    }
+
     /// Computes the dot product of two points.
     ///
     /// # Examples
@@ -81,13 +106,367 @@ impl Point {
         // dot product -- multiply each non-zero term and sum
         self.0.iter().zip(&other.0).map(|(&a, &b)| a * b).sum()
     }
+
+    /// Computes the outer product of two points, the matrix
+    /// whose (i, j) entry is `aᵢ * conj(bⱼ)`.
+    ///
+    /// The outer product of a point with itself is Hermitian.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    /// ```
+    /// # use data_utils::{Complex, Point};
+    /// let a = Point(vec![1.0.into(), Complex { re: 0.0, im: 1.0 }]);
+    /// let outer = a.outer(&a);
+    /// assert_eq!(outer[0][1], Complex { re: 0.0, im: -1.0 });
+    /// assert_eq!(outer[1][0], Complex { re: 0.0, im: 1.0 });
+    /// ```
+    #[must_use]
+    pub fn outer(&self, other: &Self) -> Matrix {
+        let rows = self
+            .0
+            .iter()
+            .map(|&a| other.0.iter().map(|b| a * b.conjugate()).collect())
+            .collect();
+        Matrix { rows }
+    }
+}
+
+/// Stores a matrix of complex numbers as a list of rows.
+#[derive(Clone, Default, PartialEq)]
+pub struct Matrix {
+    rows: Vec<Vec<Complex>>,
+}
+
+impl Debug for Matrix {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{:?}", self.rows)
+    }
+}
+
+/// The reason a matrix could not be inverted.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum MatrixError {
+    /// The matrix does not have as many rows as columns
+    NotSquare,
+    /// The matrix has a determinant of 0
+    Singular,
+}
+
+impl Display for MatrixError {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::NotSquare => write!(f, "Matrix is not square"),
+            Self::Singular => write!(f, "Matrix is singular"),
+        }
+    }
+}
+
+impl Error for MatrixError {}
+
+impl Matrix {
+    /// Creates a matrix from a list of rows.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the rows do not all have the same length.
+    #[inline]
+    #[must_use]
+    pub fn from_rows(rows: Vec<Vec<Complex>>) -> Self {
+        assert!(
+            rows.windows(2).all(|w| w[0].len() == w[1].len()),
+            "The rows of a matrix must have the same length"
+        );
+        Self { rows }
+    }
+
+    /// Creates a matrix of zeros with the given number of rows and columns.
+    #[inline]
+    #[must_use]
+    pub fn zeros(num_rows: usize, num_cols: usize) -> Self {
+        Self {
+            rows: vec![vec![Complex::default(); num_cols]; num_rows],
+        }
+    }
+
+    /// Creates an identity matrix of the given size.
+    #[must_use]
+    pub fn identity(n: usize) -> Self {
+        let mut matrix = Self::zeros(n, n);
+        for i in 0..n {
+            matrix.rows[i][i] = 1.0.into();
+        }
+        matrix
+    }
+
+    /// Gets the number of rows of the matrix.
+    #[inline]
+    #[must_use]
+    pub fn num_rows(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Gets the number of columns of the matrix.
+    #[inline]
+    #[must_use]
+    pub fn num_cols(&self) -> usize {
+        self.rows.first().map_or(0, Vec::len)
+    }
+
+    /// Gets the rows of the matrix.
+    #[inline]
+    #[must_use]
+    pub fn rows(&self) -> &[Vec<Complex>] {
+        &self.rows
+    }
+
+    /// Converts the matrix into its rows.
+    #[inline]
+    #[must_use]
+    pub fn into_rows(self) -> Vec<Vec<Complex>> {
+        self.rows
+    }
+
+    /// Transposes the matrix, swapping its rows and columns.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    /// ```
+    /// # use data_utils::lin_alg::Matrix;
+    /// let matrix = Matrix::from_rows(vec![vec![1.0.into(), 2.0.into(), 3.0.into()]]);
+    /// let transpose = matrix.transpose();
+    /// assert_eq!((transpose.num_rows(), transpose.num_cols()), (3, 1));
+    /// assert_eq!(transpose[2][0], 3.0.into());
+    /// ```
+    #[must_use]
+    pub fn transpose(&self) -> Self {
+        let rows = (0..self.num_cols())
+            .map(|j| self.rows.iter().map(|row| row[j]).collect())
+            .collect();
+        Self { rows }
+    }
+
+    /// Inverts a square matrix using Gauss-Jordan elimination with partial
+    /// pivoting, returning the inverse and the determinant of the matrix.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the matrix is not square or is singular.
+    pub fn inverse_with_determinant(&self) -> Result<(Self, Complex), MatrixError> {
+        /// Pivots with a magnitude below this are treated as zero.
+        const SINGULAR_EPSILON: f64 = 1e-12;
+
+        let n = self.num_rows();
+        if self.num_cols() != n {
+            return Err(MatrixError::NotSquare);
+        }
+
+        // augment the matrix with the identity matrix: [A | I]
+        let mut rows: Vec<Vec<Complex>> = self
+            .rows
+            .iter()
+            .zip(Self::identity(n).rows)
+            .map(|(row, identity_row)| [row.as_slice(), &identity_row].concat())
+            .collect();
+
+        let mut det = Complex::from(1.0);
+        for col in 0..n {
+            // pick the row with the largest pivot to reduce rounding error
+            let pivot_row = (col..n)
+                .max_by(|&a, &b| {
+                    let a = rows[a][col];
+                    let b = rows[b][col];
+                    a.re.hypot(a.im).total_cmp(&b.re.hypot(b.im))
+                })
+                .ok_or(MatrixError::Singular)?;
+            let pivot = rows[pivot_row][col];
+            if pivot.re.hypot(pivot.im) < SINGULAR_EPSILON {
+                return Err(MatrixError::Singular);
+            }
+            if pivot_row != col {
+                rows.swap(pivot_row, col);
+                // swapping rows negates the determinant
+                det = -det;
+            }
+            det *= pivot;
+
+            // scale the pivot row so the pivot is 1
+            for x in &mut rows[col] {
+                *x /= pivot;
+            }
+            // eliminate the pivot column from all other rows
+            let pivot_vals = rows[col].clone();
+            for (i, row) in rows.iter_mut().enumerate() {
+                if i == col {
+                    continue;
+                }
+                let factor = row[col];
+                for (x, &p) in row.iter_mut().zip(&pivot_vals) {
+                    *x -= factor * p;
+                }
+            }
+        }
+
+        // the right half is now the inverse: [I | A^-1]
+        let rows = rows.into_iter().map(|row| row[n..].to_vec()).collect();
+        Ok((Self { rows }, det))
+    }
+
+    /// Inverts a square matrix using Gauss-Jordan elimination.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the matrix is not square or is singular.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    /// ```
+    /// # use data_utils::lin_alg::{Matrix, MatrixError};
+    /// let matrix = Matrix::from_rows(vec![vec![4.0.into(), 7.0.into()], vec![2.0.into(), 6.0.into()]]);
+    /// let inverse = matrix.inverse().unwrap();
+    /// assert!((inverse[0][1].re + 0.7).abs() < 1e-12);
+    ///
+    /// let singular = Matrix::from_rows(vec![vec![1.0.into(), 2.0.into()], vec![2.0.into(), 4.0.into()]]);
+    /// assert_eq!(singular.inverse(), Err(MatrixError::Singular));
+    /// ```
+    #[inline]
+    pub fn inverse(&self) -> Result<Self, MatrixError> {
+        self.inverse_with_determinant().map(|(inverse, _)| inverse)
+    }
+}
+
+impl Index<usize> for Matrix {
+    type Output = [Complex];
+
+    #[inline]
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.rows[index]
+    }
+}
+
+impl IndexMut<usize> for Matrix {
+    #[inline]
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.rows[index]
+    }
+}
+
+impl Mul<&Point> for &Matrix {
+    type Output = Point;
+
+    /// Multiplies the matrix by a column vector, where dimensions
+    /// not present in the point are 0.
+    #[inline]
+    fn mul(self, rhs: &Point) -> Self::Output {
+        Point(
+            self.rows
+                .iter()
+                .map(|row| row.iter().zip(&rhs.0).map(|(&a, &x)| a * x).sum())
+                .collect(),
+        )
+    }
+}
+
+impl Mul for &Matrix {
+    type Output = Matrix;
+
+    /// Multiplies two matrices.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of columns of the left matrix does
+    /// not match the number of rows of the right matrix.
+    fn mul(self, rhs: Self) -> Self::Output {
+        assert_eq!(
+            self.num_cols(),
+            rhs.num_rows(),
+            "Cannot multiply matrices with mismatched dimensions"
+        );
+
+        let rows = self
+            .rows
+            .iter()
+            .map(|row| {
+                (0..rhs.num_cols())
+                    .map(|j| row.iter().zip(&rhs.rows).map(|(&a, b)| a * b[j]).sum())
+                    .collect()
+            })
+            .collect();
+        Matrix { rows }
+    }
+}
+
+impl Mul for Matrix {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, rhs: Self) -> Self::Output {
+        &self * &rhs
+    }
+}
+
+/// Computes the Gram matrix of the given points, the matrix
+/// whose (i, j) entry is the dot product of points i and j.
+///
+/// The Gram matrix of real points is symmetric.
+///
+/// # Examples
+///
+/// Basic usage:
+/// ```
+/// # use data_utils::{lin_alg::gram_matrix, Point};
+/// let points = [Point(vec![1.0.into(), 2.0.into()]), Point(vec![3.0.into(), 4.0.into()])];
+/// let gram = gram_matrix(&points);
+/// assert_eq!(gram[0][1], 11.0.into());
+/// assert_eq!(gram[1][0], 11.0.into());
+/// ```
+#[must_use]
+pub fn gram_matrix(points: &[Point]) -> Matrix {
+    let rows = points
+        .iter()
+        .map(|a| points.iter().map(|b| a.dot(b)).collect())
+        .collect();
+    Matrix { rows }
+}
+
+impl FromStr for Point {
+    type Err = Box<dyn Error>;
+
+    /// Parses a point from a line of unlabeled data, where
+    /// every whitespace-separated value is a component.
+    ///
+    /// Unlike parsing a [`DataPoint`](crate::DataPoint), the last
+    /// value is not treated as the classification.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    /// ```
+    /// # use data_utils::Point;
+    /// let point = "0.1 0.2 0.3".parse::<Point>().unwrap();
+    /// assert_eq!(point, Point(vec![0.1.into(), 0.2.into(), 0.3.into()]));
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let point = s
+            .split_whitespace()
+            .map(str::parse)
+            .collect::<Result<Vec<_>, _>>()?;
+        if point.is_empty() {
+            return Err("Cannot parse empty line of data".into());
+        }
+        Ok(Self(point))
+    }
 }
 
 impl Neg for Point {
     type Output = Self;
 
     #[inline]
-    #[must_use]
     fn neg(self) -> Self::Output {
         self.scale(-1.0)
     }
@@ -96,7 +475,6 @@ impl Neg for Point {
 impl Add for Point {
     type Output = Self;
 
-    #[must_use]
     fn add(self, rhs: Self) -> Self::Output {
         // use whichever point has more dimensions
         if self.0.len() >= rhs.0.len() {
@@ -120,7 +498,6 @@ impl Add for Point {
 impl Add for &Point {
     type Output = Point;
 
-    #[must_use]
     fn add(self, rhs: Self) -> Self::Output {
         // use whichever point has more dimensions
         if self.0.len() >= rhs.0.len() {
@@ -144,7 +521,6 @@ impl Add for &Point {
 impl Add<&Self> for Point {
     type Output = Self;
 
-    #[must_use]
     fn add(self, rhs: &Self) -> Self::Output {
         // self + rhs, where dimensions not present in rhs are 0
         let mut point = self;
@@ -160,7 +536,6 @@ impl Add<&Self> for Point {
 impl Add<Point> for &Point {
     type Output = Point;
 
-    #[must_use]
     fn add(self, rhs: Point) -> Self::Output {
         // self + rhs, where dimensions not present in rhs are 0
         let mut point = rhs;
@@ -194,7 +569,6 @@ impl AddAssign for Point {
 impl Sub for Point {
     type Output = Self;
 
-    #[must_use]
     fn sub(self, rhs: Self) -> Self::Output {
         // use whichever point has more dimensions
         if self.0.len() >= rhs.0.len() {
@@ -218,7 +592,6 @@ impl Sub for Point {
 impl Sub for &Point {
     type Output = Point;
 
-    #[must_use]
     fn sub(self, rhs: Self) -> Self::Output {
         // use whichever point has more dimensions
         if self.0.len() >= rhs.0.len() {
@@ -242,7 +615,6 @@ impl Sub for &Point {
 impl Sub<&Self> for Point {
     type Output = Self;
 
-    #[must_use]
     fn sub(self, rhs: &Self) -> Self::Output {
         // self - rhs, where dimensions not present in rhs are 0
         let mut point = self;
@@ -260,7 +632,6 @@ impl Sub<&Self> for Point {
 impl Sub<Point> for &Point {
     type Output = Point;
 
-    #[must_use]
     fn sub(self, rhs: Point) -> Self::Output {
         // -(rhs - self), where dimensions not present in self are 0
         let mut point = rhs;
@@ -295,7 +666,6 @@ impl SubAssign for Point {
 
 impl Sum for Point {
     #[inline]
-    #[must_use]
     fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
         iter.fold(Self::default(), |a, b| a + b)
     }
@@ -303,7 +673,6 @@ impl Sum for Point {
 
 impl<'a> Sum<&'a Self> for Point {
     #[inline]
-    #[must_use]
     fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
         iter.fold(Self::default(), |a, b| a + b)
     }
@@ -327,4 +696,110 @@ mod test {
         let sum_iter: Point = [a, b].iter().sum();
         assert_eq!(sum_iter, sum);
     }
+
+    #[test]
+    fn point_from_unlabeled() {
+        let point = "0.1 0.2+1i   0.3".parse::<Point>().unwrap();
+        assert_eq!(
+            point,
+            Point(vec![0.1.into(), Complex { re: 0.2, im: 1.0 }, 0.3.into()])
+        );
+
+        assert!("0.1 0.2 red".parse::<Point>().is_err());
+        assert!("".parse::<Point>().is_err());
+    }
+
+    /// Creates a matrix of real numbers from a list of rows.
+    fn matrix(rows: &[&[f64]]) -> Matrix {
+        Matrix::from_rows(
+            rows.iter()
+                .map(|row| row.iter().map(|&x| x.into()).collect())
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn point_outer() {
+        let a = Point(vec![1.0.into(), 2.0.into()]);
+        assert_eq!(a.outer(&a), matrix(&[&[1.0, 2.0], &[2.0, 4.0]]));
+
+        let b = Point(vec![3.0.into(), 4.0.into(), 5.0.into()]);
+        assert_eq!(a.outer(&b), matrix(&[&[3.0, 4.0, 5.0], &[6.0, 8.0, 10.0]]));
+        assert_eq!(a.outer(&b).transpose(), b.outer(&a));
+    }
+
+    #[test]
+    fn gram() {
+        let points = [
+            Point(vec![1.0.into(), 0.0.into()]),
+            Point(vec![1.0.into(), 2.0.into()]),
+            Point(vec![3.0.into(), 4.0.into()]),
+        ];
+        let gram = gram_matrix(&points);
+        assert_eq!((gram.num_rows(), gram.num_cols()), (3, 3));
+        assert_eq!(gram, gram.transpose());
+
+        // the diagonal is the squared magnitude of each point
+        for (i, point) in points.iter().enumerate() {
+            assert!((gram[i][i].re - point.magnitude().powi(2)).abs() < 1e-12);
+        }
+        assert_eq!(gram[1][2], 11.0.into());
+        assert_eq!(gram_matrix(&[]), Matrix::default());
+    }
+
+    #[test]
+    fn matrix_transpose() {
+        let a = matrix(&[&[1.0, 2.0, 3.0], &[4.0, 5.0, 6.0]]);
+        let t = a.transpose();
+        assert_eq!(t, matrix(&[&[1.0, 4.0], &[2.0, 5.0], &[3.0, 6.0]]));
+        assert_eq!(t.transpose(), a);
+        assert_eq!(Matrix::default().transpose(), Matrix::default());
+    }
+
+    #[test]
+    fn matrix_inverse() {
+        let a = matrix(&[&[4.0, 7.0], &[2.0, 6.0]]);
+        let (inverse, det) = a.inverse_with_determinant().unwrap();
+        assert!((det.re - 10.0).abs() < 1e-12);
+        let expected = matrix(&[&[0.6, -0.7], &[-0.2, 0.4]]);
+        for (x, y) in inverse
+            .rows()
+            .iter()
+            .flatten()
+            .zip(expected.rows().iter().flatten())
+        {
+            assert!((*x - *y).magnitude() < 1e-12);
+        }
+
+        // A A⁻¹ = I
+        let product = &a * &inverse;
+        for (x, y) in product
+            .rows()
+            .iter()
+            .flatten()
+            .zip(Matrix::identity(2).rows().iter().flatten())
+        {
+            assert!((*x - *y).magnitude() < 1e-12);
+        }
+
+        assert_eq!(
+            matrix(&[&[1.0, 2.0], &[2.0, 4.0]]).inverse(),
+            Err(MatrixError::Singular)
+        );
+        assert_eq!(
+            matrix(&[&[1.0, 2.0]]).inverse(),
+            Err(MatrixError::NotSquare)
+        );
+    }
+
+    #[test]
+    fn matrix_identity_mul() {
+        let a = matrix(&[&[1.0, 2.0, 3.0], &[4.0, 5.0, 6.0]]);
+        assert_eq!(&Matrix::identity(2) * &a, a);
+        assert_eq!(&a * &Matrix::identity(3), a);
+
+        let point = Point(vec![1.0.into(), 2.0.into(), 3.0.into()]);
+        assert_eq!(&Matrix::identity(3) * &point, point);
+        assert_eq!(&a * &point, Point(vec![14.0.into(), 32.0.into()]));
+    }
 }
@@ -0,0 +1,42 @@
+//! This module re-exports the most commonly used
+//! types and functions of the crate.
+//!
+//! # Examples
+//!
+//! Basic usage:
+//! ```
+//! use data_utils::prelude::*;
+//!
+//! let train_data: Vec<DataPoint<String>> = ["0 0 0 black", "255 255 255 white"]
+//!     .into_iter()
+//!     .map(|line| line.parse().unwrap())
+//!     .collect();
+//!
+//! let color = Rgb { r: 200, g: 210, b: 220 };
+//! assert!(Hsv::from(color).s < 0.1);
+//! let test_data = [DataPoint {
+//!     point: Point(vec![
+//!         Complex::from(f64::from(color.r)),
+//!         Complex::from(f64::from(color.g)),
+//!         Complex::from(f64::from(color.b)),
+//!     ]),
+//!     class: String::from("white"),
+//!     weight: 1.0,
+//!     id: None,
+//! }];
+//!
+//! let results: Vec<Classification<String>> = k_nearest_neighbor(&train_data, &test_data, 1);
+//! assert_eq!(results[0].class_guess, "white");
+//! ```
+//!
+//! Author: Benjamin Hall
+
+pub use crate::{
+    classify::{
+        accuracy, bayes_plug_in, classification_report, cross_validate, k_nearest_neighbor,
+        multiclass_single_layer_perceptron, single_layer_perceptron, train_test_split,
+        Classification, Classifier, PerceptronConfig, Standardizer,
+    },
+    color::{Cmyk, Color, Hsl, Hsv, Lab, Rgb, Rgba, Xyz, YCbCr},
+    Complex, DataPoint, DataSet, Point,
+};
@@ -21,6 +21,21 @@ pub trait PartialSort<T> {
     fn partial_sort_by<F>(&mut self, num_sorted: usize, compare: F)
     where
         F: FnMut(&T, &T) -> Ordering;
+
+    /// Checks if the first `num_sorted` elements are sorted
+    /// with the given comparison function, and that none of
+    /// the remaining elements are less than them.
+    fn is_sorted_to<F>(&self, num_sorted: usize, compare: F) -> bool
+    where
+        F: FnMut(&T, &T) -> Ordering;
+
+    /// Partially sorts the first `num_sorted` elements
+    /// with the given comparison function, in parallel.
+    #[cfg(feature = "parallel")]
+    fn par_partial_sort_by<F>(&mut self, num_sorted: usize, compare: F)
+    where
+        T: Send,
+        F: Fn(&T, &T) -> Ordering + Sync;
 }
 
 impl<T> PartialSort<T> for [T] {
@@ -42,6 +57,117 @@ impl<T> PartialSort<T> for [T] {
             }
         }
     }
+
+    /// Checks if the first `num_sorted` elements of a slice are sorted
+    /// with the given comparison function, and that none of the
+    /// remaining elements are less than them.
+    ///
+    /// If `num_sorted` is greater than the length of the slice,
+    /// the entire slice is checked.
+    fn is_sorted_to<F>(&self, num_sorted: usize, mut compare: F) -> bool
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let num_sorted = num_sorted.min(self.len());
+        let (sorted, rest) = self.split_at(num_sorted);
+
+        // the sorted elements must be in order
+        let in_order = sorted.windows(2).all(|w| compare(&w[0], &w[1]).is_le());
+        // the largest sorted element must be no greater than the rest
+        in_order
+            && sorted
+                .last()
+                .is_none_or(|last| rest.iter().all(|r| compare(last, r).is_le()))
+    }
+
+    /// Partially sorts the first `num_sorted` elements of a slice
+    /// with the given comparison function, in parallel.
+    ///
+    /// This implementation uses a partial quicksort. After each
+    /// partition, the selection of the remaining elements runs in
+    /// parallel with the sorting of the elements before the pivot.
+    #[cfg(feature = "parallel")]
+    #[inline]
+    fn par_partial_sort_by<F>(&mut self, num_sorted: usize, compare: F)
+    where
+        T: Send,
+        F: Fn(&T, &T) -> Ordering + Sync,
+    {
+        par_partial_quicksort(self, num_sorted.min(self.len()), &compare);
+    }
+}
+
+/// Slices at or below this length are partially sorted sequentially.
+#[cfg(feature = "parallel")]
+const PAR_SEQUENTIAL_LEN: usize = 64;
+
+/// Recursively partially sorts the first `num_sorted` elements of a slice,
+/// running independent partitions in parallel.
+#[cfg(feature = "parallel")]
+fn par_partial_quicksort<T, F>(slice: &mut [T], num_sorted: usize, compare: &F)
+where
+    T: Send,
+    F: Fn(&T, &T) -> Ordering + Sync,
+{
+    if num_sorted == 0 {
+        return;
+    }
+    if slice.len() <= PAR_SEQUENTIAL_LEN {
+        slice.partial_sort_by(num_sorted, compare);
+        return;
+    }
+
+    // partition around the median of the first, middle, and last elements
+    let pivot = partition(slice, compare);
+    let (left, right) = slice.split_at_mut(pivot);
+    // the pivot is now in its final position
+    let right = &mut right[1..];
+
+    if num_sorted <= left.len() {
+        // everything that needs sorting is left of the pivot
+        par_partial_quicksort(left, num_sorted, compare);
+    } else {
+        // sort everything left of the pivot while selecting from the right
+        let left_len = left.len();
+        rayon::join(
+            || par_partial_quicksort(left, left_len, compare),
+            || par_partial_quicksort(right, num_sorted - left_len - 1, compare),
+        );
+    }
+}
+
+/// Partitions a slice around a median-of-three pivot, returning the pivot's
+/// final index. Elements less than the pivot are moved before it.
+#[cfg(feature = "parallel")]
+fn partition<T, F>(slice: &mut [T], compare: &F) -> usize
+where
+    F: Fn(&T, &T) -> Ordering,
+{
+    let last = slice.len() - 1;
+    let mid = last / 2;
+
+    // order the first, middle, and last elements so the median is in the middle
+    if compare(&slice[mid], &slice[0]).is_lt() {
+        slice.swap(mid, 0);
+    }
+    if compare(&slice[last], &slice[0]).is_lt() {
+        slice.swap(last, 0);
+    }
+    if compare(&slice[last], &slice[mid]).is_lt() {
+        slice.swap(last, mid);
+    }
+    // move the pivot to the end of the slice
+    slice.swap(mid, last);
+
+    let mut store = 0;
+    for i in 0..last {
+        if compare(&slice[i], &slice[last]).is_lt() {
+            slice.swap(i, store);
+            store += 1;
+        }
+    }
+    slice.swap(store, last);
+    store
 }
 
 #[cfg(test)]
@@ -50,8 +176,44 @@ mod test {
 
     #[test]
     fn partial_sort_vec() {
-        let mut vec = vec![1.0, 5.0, 4.0, 7.0, 3.0];
+        let mut vec = [1.0, 5.0, 4.0, 7.0, 3.0];
         vec.partial_sort_by(3, f64::total_cmp);
         assert_eq!(vec[..3], [1.0, 3.0, 4.0]);
     }
+
+    #[test]
+    fn partial_sort_is_sorted_to() {
+        let mut arr = [1.0, 5.0, 4.0, 7.0, 3.0];
+        arr.partial_sort_by(3, f64::total_cmp);
+        assert!(arr.is_sorted_to(3, f64::total_cmp));
+    }
+
+    #[test]
+    fn is_sorted_to() {
+        let partially_sorted = [1.0, 3.0, 4.0, 7.0, 5.0];
+        assert!(partially_sorted.is_sorted_to(3, f64::total_cmp));
+
+        // first elements are out of order
+        let unsorted = [3.0, 1.0, 4.0, 7.0, 5.0];
+        assert!(!unsorted.is_sorted_to(3, f64::total_cmp));
+
+        // first elements are in order, but a smaller element remains
+        let unsorted = [1.0, 4.0, 5.0, 7.0, 3.0];
+        assert!(!unsorted.is_sorted_to(3, f64::total_cmp));
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn par_partial_sort_vec() {
+        let mut rng = fastrand::Rng::with_seed(0);
+        let vec: Vec<_> = (0..10_000).map(|_| rng.f64()).collect();
+
+        let mut seq = vec.clone();
+        seq.partial_sort_by(100, f64::total_cmp);
+        let mut par = vec;
+        par.par_partial_sort_by(100, f64::total_cmp);
+
+        assert_eq!(seq[..100], par[..100]);
+        assert!(par.is_sorted_to(100, f64::total_cmp));
+    }
 }
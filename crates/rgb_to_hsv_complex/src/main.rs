@@ -7,7 +7,7 @@
 use std::{
     env,
     fs::File,
-    io::{self, BufWriter, Write},
+    io::{self, Write},
 };
 
 use data_utils::{
@@ -89,28 +89,26 @@ fn main() {
     };
 
     // convert to HSV output data
-    let output_data = input_data.into_iter().map(|mut d| {
-        let [r, g, b] = d.point.0[0..3] else { unreachable!() };
-        let (r, g, b) = (r.re as u8, g.re as u8, b.re as u8);
-        let Hsv { h, s, v } = Hsv::from(Rgb { r, g, b });
-        d.point.0[0..3].copy_from_slice(&[
-            // complex value on the unit circle
-            Complex::from_polar(1.0, h.to_radians()),
-            s.into(),
-            v.into(),
-        ]);
-        d.to_string()
-    });
+    let output_data: Vec<_> = input_data
+        .into_iter()
+        .map(|mut d| {
+            let [r, g, b] = d.point.0[0..3] else {
+                unreachable!()
+            };
+            let (r, g, b) = (r.re as u8, g.re as u8, b.re as u8);
+            let Hsv { h, s, v } = Hsv::from(Rgb { r, g, b });
+            d.point.0[0..3].copy_from_slice(&[
+                // complex value on the unit circle
+                Complex::from_polar(1.0, h.to_radians()),
+                s.into(),
+                v.into(),
+            ]);
+            d
+        })
+        .collect();
 
-    {
-        let Ok(output_file) = File::create(output_data_file_name) else {
-            println!("Error: could not open output file");
-            return;
-        };
-        let mut writer = BufWriter::new(output_file);
-        for line in output_data {
-            writeln!(writer, "{line}").unwrap();
-        }
-        writer.flush().unwrap();
+    // write output data
+    if data_utils::io::write_data_points(output_data_file_name, &output_data).is_err() {
+        println!("Error: could not write output file");
     }
 }